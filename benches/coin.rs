@@ -0,0 +1,95 @@
+use std::hint::black_box;
+
+use coin::{WideCoin, Word};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+// Pinned, whichever `width-*` feature is enabled, to match the patterns below.
+type Coin = WideCoin<u8>;
+
+// A canonical pattern and one that has suffered 3 bit flips.
+const PATTERNS: [(&str, u8); 2] = [("clean", u8::MAX), ("flipped", 0b1101_1010)];
+
+fn to_bool(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_bool");
+
+    let b = true;
+    group.bench_function("bool", |bench| bench.iter(|| black_box(&b) == &true));
+
+    for (name, bits) in PATTERNS {
+        let coin = Coin::from_bits(bits);
+        group.bench_function(name, |bench| bench.iter(|| black_box(&coin).to_bool()));
+    }
+
+    group.finish();
+}
+
+fn eq(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eq");
+
+    let (a, b) = (true, true);
+    group.bench_function("bool", |bench| bench.iter(|| black_box(&a) == black_box(&b)));
+
+    for (name, bits) in PATTERNS {
+        let (x, y) = (Coin::from_bits(bits), Coin::from_bits(bits));
+        group.bench_function(name, |bench| bench.iter(|| black_box(&x) == black_box(&y)));
+    }
+
+    group.finish();
+}
+
+fn scrub(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scrub");
+
+    for (name, bits) in PATTERNS {
+        group.bench_function(name, |bench| {
+            bench.iter(|| {
+                let coin = Coin::from_bits(black_box(bits));
+                coin.scrub();
+                coin
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn scrub_all(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scrub_all");
+
+    // Every eighth coin has suffered a bit flip.
+    let coins = || -> Vec<Coin> {
+        (0..1 << 16)
+            .map(|i| Coin::from_bits(if i % 8 == 0 { 0b1111_0111 } else { u8::MAX }))
+            .collect()
+    };
+
+    group.bench_function("each", |bench| {
+        bench.iter_batched(
+            coins,
+            |coins| coins.iter().map(|coin| u64::from(coin.scrub_reporting())).sum::<u64>(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("slice", |bench| {
+        bench.iter_batched(coins, |coins| coin::scrub_all(&coins), BatchSize::LargeInput)
+    });
+
+    group.finish();
+}
+
+fn width<W: Word>(c: &mut Criterion, name: &str) {
+    let coin = WideCoin::<W>::from(true);
+    c.bench_function(&format!("to_bool/width/{name}"), |bench| {
+        bench.iter(|| black_box(&coin).to_bool())
+    });
+}
+
+fn widths(c: &mut Criterion) {
+    width::<u8>(c, "u8");
+    width::<u16>(c, "u16");
+    width::<u32>(c, "u32");
+    width::<u64>(c, "u64");
+}
+
+criterion_group!(benches, to_bool, eq, scrub, scrub_all, widths);
+criterion_main!(benches);
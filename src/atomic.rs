@@ -0,0 +1,236 @@
+//! A coin that can be shared between threads.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::word::{canonical, decode, Word};
+use crate::WideCoin;
+
+/// A bit flip resistant Boolean type that can be shared between threads
+///
+/// [`Coin`] keeps its byte in a `Cell`, which is neither `Send` nor `Sync`,
+/// so it can't be placed in a `static`. `AtomicCoin` uses the same decoding
+/// rule, but keeps its byte in an `AtomicU8`.
+///
+/// ```
+/// # use coin::AtomicCoin;
+/// use std::sync::atomic::Ordering;
+///
+/// static ARMED: AtomicCoin = AtomicCoin::new(false);
+///
+/// ARMED.store(true, Ordering::SeqCst);
+/// assert!(ARMED.to_bool(Ordering::SeqCst));
+/// ```
+///
+/// [`Coin`]: crate::Coin
+#[derive(Debug)]
+pub struct AtomicCoin(AtomicU8);
+
+impl AtomicCoin {
+    pub const fn new(b: bool) -> Self {
+        let bits = match b {
+            true => u8::MAX,
+            false => u8::MIN,
+        };
+
+        AtomicCoin(AtomicU8::new(bits))
+    }
+
+    /// Loads the current bits into a `WideCoin<u8>`, without decoding them.
+    pub fn load(&self, order: Ordering) -> WideCoin<u8> {
+        WideCoin::from_bits(self.0.load(order))
+    }
+
+    /// Stores the canonical bit pattern for `b`.
+    pub fn store(&self, b: bool, order: Ordering) {
+        self.0.store(canonical(b), order);
+    }
+
+    #[inline(always)]
+    pub fn to_bool(&self, order: Ordering) -> bool {
+        decode(self.0.load(order))
+    }
+
+    /// Rewrites the canonical bit pattern for the current value.
+    ///
+    /// A compare-and-swap loop ensures that a concurrent `store` is never
+    /// overwritten with a stale value.
+    pub fn degauss(&self, order: Ordering) {
+        self.degauss_reporting(order);
+    }
+
+    /// Stores the canonical bit pattern for `new` if the coin decodes as
+    /// `current`.
+    ///
+    /// Returns the previous decoded value, `Ok` if it was `current` and the
+    /// store happened, or `Err` if it wasn't. A concurrent change to the bits
+    /// that doesn't change the decoded value, such as a `degauss`, doesn't
+    /// cause a spurious failure.
+    ///
+    /// ```
+    /// # use coin::AtomicCoin;
+    /// use std::sync::atomic::Ordering::SeqCst;
+    ///
+    /// let armed = AtomicCoin::new(false);
+    /// assert_eq!(armed.compare_exchange(false, true, SeqCst, SeqCst), Ok(false));
+    /// assert_eq!(armed.compare_exchange(false, true, SeqCst, SeqCst), Err(true));
+    /// ```
+    pub fn compare_exchange(
+        &self,
+        current: bool,
+        new: bool,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<bool, bool> {
+        let mut bits = self.0.load(failure);
+        loop {
+            let observed = decode(bits);
+            if observed != current {
+                return Err(observed);
+            }
+
+            match self.0.compare_exchange_weak(bits, canonical(new), success, failure) {
+                Ok(_) => return Ok(observed),
+                Err(actual) => bits = actual,
+            }
+        }
+    }
+
+    fn degauss_reporting(&self, order: Ordering) -> u32 {
+        let load_order = match order {
+            Ordering::Release => Ordering::Relaxed,
+            Ordering::AcqRel => Ordering::Acquire,
+            order => order,
+        };
+
+        let mut current = self.0.load(load_order);
+        loop {
+            let fresh_bits: u8 = canonical(decode(current));
+            if current == fresh_bits {
+                return 0;
+            }
+
+            match self.0.compare_exchange_weak(current, fresh_bits, order, load_order) {
+                Ok(_) => return Word::count_ones(current ^ fresh_bits),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+/// Degausses every coin in `coins`, returning the total number of corrected
+/// bits.
+pub fn scrub_all_atomic(coins: &[AtomicCoin], order: Ordering) -> u64 {
+    coins.iter().map(|coin| u64::from(coin.degauss_reporting(order))).sum()
+}
+
+impl From<bool> for AtomicCoin {
+    #[inline(always)]
+    fn from(b: bool) -> Self {
+        AtomicCoin::new(b)
+    }
+}
+
+impl From<AtomicCoin> for bool {
+    #[inline(always)]
+    fn from(c: AtomicCoin) -> Self {
+        decode(c.0.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AtomicCoin;
+    use core::sync::atomic::Ordering;
+    use std::vec::Vec;
+
+    type Coin = crate::WideCoin<u8>;
+
+    #[test]
+    fn scrub_all_atomic() {
+        let coins = [AtomicCoin::new(true), AtomicCoin::new(false), AtomicCoin::new(false)];
+        coins[0].0.store(0b0111_1110, Ordering::Relaxed);
+        coins[2].0.store(0b0100_0000, Ordering::Relaxed);
+
+        assert_eq!(super::scrub_all_atomic(&coins, Ordering::AcqRel), 3);
+        assert_eq!(coins[0].0.load(Ordering::Relaxed), u8::MAX);
+        assert_eq!(coins[2].0.load(Ordering::Relaxed), u8::MIN);
+    }
+
+    #[test]
+    fn atomic_coin_round_trip() {
+        let coin = AtomicCoin::from(true);
+        assert!(coin.to_bool(Ordering::SeqCst));
+
+        coin.store(false, Ordering::SeqCst);
+        assert_eq!(coin.load(Ordering::SeqCst), Coin::from(false));
+        assert!(!bool::from(coin));
+    }
+
+    #[test]
+    fn atomic_coin_degauss() {
+        let coin = AtomicCoin::new(false);
+        coin.0.store(0b0001_0010, Ordering::SeqCst);
+        coin.degauss(Ordering::AcqRel);
+        assert_eq!(coin.0.load(Ordering::SeqCst), u8::MIN);
+    }
+
+    #[test]
+    fn atomic_coin_shared_between_threads() {
+        static SHARED: AtomicCoin = AtomicCoin::new(true);
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    for _ in 0..10_000 {
+                        if i == 0 {
+                            SHARED.0.store(0b1101_1011, Ordering::SeqCst);
+                        }
+                        assert!(SHARED.to_bool(Ordering::SeqCst));
+                        SHARED.degauss(Ordering::SeqCst);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        SHARED.degauss(Ordering::SeqCst);
+        assert_eq!(SHARED.0.load(Ordering::SeqCst), u8::MAX);
+    }
+
+    #[test]
+    fn compare_exchange_decodes_the_current_bits() {
+        let coin = AtomicCoin::new(false);
+        coin.0.store(0b0010_0100, Ordering::SeqCst);
+        assert_eq!(coin.compare_exchange(true, false, Ordering::SeqCst, Ordering::SeqCst), Err(false));
+        assert_eq!(coin.0.load(Ordering::SeqCst), 0b0010_0100);
+
+        assert_eq!(coin.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst), Ok(false));
+        assert_eq!(coin.0.load(Ordering::SeqCst), u8::MAX);
+    }
+
+    #[test]
+    fn compare_exchange_has_one_winner() {
+        use std::sync::{Arc, Barrier};
+
+        let coin = Arc::new(AtomicCoin::new(false));
+        let barrier = Arc::new(Barrier::new(8));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let (coin, barrier) = (Arc::clone(&coin), Arc::clone(&barrier));
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    coin.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(results.iter().all(|result| *result == Ok(false) || *result == Err(true)));
+        assert!(coin.to_bool(Ordering::SeqCst));
+    }
+}
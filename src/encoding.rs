@@ -0,0 +1,347 @@
+//! Alternative encodings of a Boolean value in a byte.
+
+use core::cell::Cell;
+
+use crate::error::CoinError;
+use crate::word::{decode, Word};
+
+/// A bit flip resistant Boolean type that avoids all-ones and all-zeros
+///
+/// [`Coin`](crate::Coin) stores `true` as `0xFF` and `false` as `0x00`, the
+/// two patterns that a stuck data line or a decaying SRAM cell most readily
+/// produce. `GrayCoin` stores `true` as `0b1010_1010` and `false` as
+/// `0b0101_0101` instead. The two patterns differ in every bit, so they are
+/// as far apart as two bytes can be, and neither is a run of identical bits.
+///
+/// Decoding picks whichever pattern is nearer. Either value tolerates 3 bit
+/// flips. A byte that is equally far from both decodes as `true`, the same
+/// tie-break that `Coin` uses.
+#[derive(Debug, Clone)]
+pub struct GrayCoin(Cell<u8>);
+
+impl GrayCoin {
+    const TRUE: u8 = 0b1010_1010;
+    const FALSE: u8 = 0b0101_0101;
+
+    pub const fn new(b: bool) -> Self {
+        match b {
+            true => GrayCoin(Cell::new(Self::TRUE)),
+            false => GrayCoin(Cell::new(Self::FALSE)),
+        }
+    }
+
+    /// Creates a coin that stores `bits` verbatim.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        GrayCoin(Cell::new(bits))
+    }
+
+    /// Returns the raw bits without decoding or scrubbing them.
+    #[inline]
+    pub fn as_bits(&self) -> u8 {
+        self.0.get()
+    }
+
+    /// Returns `true` when the bits are no further from the `true` pattern
+    /// than from the `false` one.
+    #[inline(always)]
+    pub fn to_bool(&self) -> bool {
+        let val = self.0.get();
+        Word::count_ones(val ^ Self::TRUE) <= Word::count_ones(val ^ Self::FALSE)
+    }
+
+    /// Rewrites the canonical bit pattern for the current value.
+    pub fn scrub(&self) {
+        let fresh_bits = match self.to_bool() {
+            true => Self::TRUE,
+            false => Self::FALSE,
+        };
+
+        self.0.set(fresh_bits);
+    }
+}
+
+impl From<bool> for GrayCoin {
+    #[inline(always)]
+    fn from(b: bool) -> Self {
+        GrayCoin::new(b)
+    }
+}
+
+impl From<GrayCoin> for bool {
+    #[inline(always)]
+    fn from(c: GrayCoin) -> Self {
+        c.to_bool()
+    }
+}
+
+/// A bit flip resistant Boolean type that decodes an odd number of bits
+///
+/// A [`Coin`](crate::Coin) with exactly 4 of its 8 bits set is tied, and decodes as
+/// `true`, so a byte of pure noise is biased that way. `Coin7` masks off the
+/// most significant bit and decodes the low 7 bits by majority, so a tie is
+/// impossible and the decode is symmetric. The masked bit is ignored.
+///
+/// The canonical patterns are `0b0111_1111` and `0b0000_0000`. Either can
+/// tolerate 3 bit flips.
+#[derive(Debug, Clone)]
+pub struct Coin7(Cell<u8>);
+
+impl Coin7 {
+    const MASK: u8 = 0b0111_1111;
+
+    pub const fn new(b: bool) -> Self {
+        match b {
+            true => Coin7(Cell::new(Self::MASK)),
+            false => Coin7(Cell::new(0)),
+        }
+    }
+
+    /// Creates a coin that stores `bits` verbatim, including the masked bit.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        Coin7(Cell::new(bits))
+    }
+
+    /// Returns the raw bits, including the masked bit.
+    #[inline]
+    pub fn as_bits(&self) -> u8 {
+        self.0.get()
+    }
+
+    /// Returns `true` when 4 or more of the low 7 bits are set.
+    #[inline(always)]
+    pub fn to_bool(&self) -> bool {
+        let val = self.0.get();
+        Word::count_ones(val & Self::MASK) > Word::count_ones(!val & Self::MASK)
+    }
+
+    /// Rewrites the canonical bit pattern for the current value.
+    pub fn scrub(&self) {
+        let fresh_bits = match self.to_bool() {
+            true => Self::MASK,
+            false => 0,
+        };
+
+        self.0.set(fresh_bits);
+    }
+}
+
+impl From<bool> for Coin7 {
+    #[inline(always)]
+    fn from(b: bool) -> Self {
+        Coin7::new(b)
+    }
+}
+
+impl From<Coin7> for bool {
+    #[inline(always)]
+    fn from(c: Coin7) -> Self {
+        c.to_bool()
+    }
+}
+
+/// A Boolean type protected by an extended Hamming(8,4) code
+///
+/// Where [`Coin`](crate::Coin) decodes by counting bits, `HammingCoin` uses a SECDED
+/// (single error correction, double error detection) code with known
+/// guarantees. Its value is stored as the data nibble `1111` or `0000`.
+/// Bits 1 to 7 hold a Hamming(7,4) codeword, with parity bits at positions
+/// 1, 2 and 4, and bit 0 holds the parity of the whole byte.
+///
+/// Any single bit flip is corrected. Any two bit flips are detected, and
+/// [`HammingCoin::try_to_bool`] reports them rather than guessing.
+#[derive(Debug, Clone)]
+pub struct HammingCoin(Cell<u8>);
+
+impl HammingCoin {
+    // Only `0000` and `1111` are valid data nibbles, and they encode to
+    // these codewords.
+    const TRUE: u8 = 0b1111_1111;
+    const FALSE: u8 = 0b0000_0000;
+
+    pub const fn new(b: bool) -> Self {
+        match b {
+            true => HammingCoin(Cell::new(Self::TRUE)),
+            false => HammingCoin(Cell::new(Self::FALSE)),
+        }
+    }
+
+    /// Creates a coin that stores `bits` verbatim.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        HammingCoin(Cell::new(bits))
+    }
+
+    /// Returns the raw bits without decoding or scrubbing them.
+    #[inline]
+    pub fn as_bits(&self) -> u8 {
+        self.0.get()
+    }
+
+    /// Decodes the coin, correcting a single bit flip.
+    ///
+    /// Returns [`CoinError::Uncorrectable`] when the bits can't be corrected,
+    /// such as after two bit flips.
+    pub fn try_to_bool(&self) -> Result<bool, CoinError> {
+        let bits = self.0.get();
+
+        let syndrome = (1..8)
+            .filter(|position| bits & (1 << position) != 0)
+            .fold(0, |syndrome, position| syndrome ^ position);
+        let parity = Word::count_ones(bits) % 2;
+
+        let corrected = match (syndrome, parity) {
+            (0, 0) => bits,
+            // A single flip breaks overall parity, and the syndrome is its position.
+            (position, 1) => bits ^ (1 << position),
+            _ => return Err(CoinError::Uncorrectable),
+        };
+
+        match corrected {
+            Self::TRUE => Ok(true),
+            Self::FALSE => Ok(false),
+            _ => Err(CoinError::Uncorrectable),
+        }
+    }
+
+    /// Decodes the coin.
+    ///
+    /// When the bits can't be corrected, this falls back to counting them
+    /// in the same way as [`Coin`](crate::Coin).
+    #[inline]
+    pub fn to_bool(&self) -> bool {
+        self.try_to_bool().unwrap_or_else(|_| decode(self.0.get()))
+    }
+
+    /// Rewrites the codeword for the current value.
+    pub fn scrub(&self) {
+        let fresh_bits = match self.to_bool() {
+            true => Self::TRUE,
+            false => Self::FALSE,
+        };
+
+        self.0.set(fresh_bits);
+    }
+}
+
+impl From<bool> for HammingCoin {
+    #[inline(always)]
+    fn from(b: bool) -> Self {
+        HammingCoin::new(b)
+    }
+}
+
+impl From<HammingCoin> for bool {
+    #[inline(always)]
+    fn from(c: HammingCoin) -> Self {
+        c.to_bool()
+    }
+}
+
+impl_bool_like!(Coin7, HammingCoin, GrayCoin);
+
+#[cfg(test)]
+mod tests {
+    use super::{Coin7, GrayCoin, HammingCoin};
+    use crate::CoinError;
+
+    #[test]
+    fn canonical_patterns() {
+        assert_eq!(GrayCoin::new(true).as_bits(), 0b1010_1010);
+        assert_eq!(GrayCoin::new(false).as_bits(), 0b0101_0101);
+        assert!(GrayCoin::from(true).to_bool());
+        assert!(!bool::from(GrayCoin::from(false)));
+    }
+
+    #[test]
+    fn tolerates_three_flips_either_way() {
+        for b in [true, false] {
+            for mask in 0..=u8::MAX {
+                let coin = GrayCoin::from_bits(GrayCoin::new(b).as_bits() ^ mask);
+
+                match mask.count_ones() {
+                    0..=3 => assert_eq!(coin.to_bool(), b, "{b} {mask:#010b}"),
+                    4 => assert!(coin.to_bool(), "{b} {mask:#010b}"),
+                    _ => assert_eq!(coin.to_bool(), !b, "{b} {mask:#010b}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_pattern_wins() {
+        // 3 flips from `true`.
+        assert!(GrayCoin::from_bits(0b1110_0011).to_bool());
+        // 3 flips from `false`, even though most bits are set.
+        assert!(!GrayCoin::from_bits(0b0111_1111).to_bool());
+        // All-ones and all-zeros are equally far from both, a tie.
+        assert!(GrayCoin::from_bits(u8::MAX).to_bool());
+        assert!(GrayCoin::from_bits(u8::MIN).to_bool());
+    }
+
+    #[test]
+    fn scrub() {
+        let coin = GrayCoin::from_bits(0b0001_0101);
+        coin.scrub();
+        assert_eq!(coin.as_bits(), 0b0101_0101);
+
+        let coin = GrayCoin::from_bits(0b1011_1011);
+        coin.scrub();
+        assert_eq!(coin.as_bits(), 0b1010_1010);
+    }
+
+    #[test]
+    fn coin7_never_ties() {
+        let mut trues = 0;
+        for bits in 0..128u8 {
+            let ones = bits.count_ones();
+            assert_ne!(ones, 7 - ones);
+
+            let coin = Coin7::from_bits(bits);
+            assert_eq!(coin.to_bool(), ones >= 4);
+            assert_eq!(Coin7::from_bits(bits | 0b1000_0000).to_bool(), coin.to_bool());
+            trues += u32::from(coin.to_bool());
+        }
+
+        assert_eq!(trues, 64);
+    }
+
+    #[test]
+    fn coin7_tolerates_three_flips() {
+        let coin = Coin7::from_bits(0b1101_0101);
+        assert!(coin.to_bool());
+        coin.scrub();
+        assert_eq!(coin.as_bits(), 0b0111_1111);
+
+        let coin = Coin7::from_bits(0b1010_1000);
+        assert!(!coin.to_bool());
+        coin.scrub();
+        assert_eq!(coin.as_bits(), 0);
+    }
+
+    #[test]
+    fn hamming_coin_corrects_single_errors() {
+        for b in [true, false] {
+            for i in 0..8 {
+                let coin = HammingCoin::from_bits(HammingCoin::from(b).as_bits() ^ (1 << i));
+                assert_eq!(coin.try_to_bool(), Ok(b));
+
+                coin.scrub();
+                assert_eq!(coin.as_bits(), HammingCoin::from(b).as_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn hamming_coin_detects_double_errors() {
+        for b in [true, false] {
+            for i in 0..8 {
+                for j in (i + 1)..8 {
+                    let coin = HammingCoin::from_bits(HammingCoin::from(b).as_bits() ^ (1 << i) ^ (1 << j));
+                    assert_eq!(coin.try_to_bool(), Err(CoinError::Uncorrectable));
+                }
+            }
+        }
+    }
+}
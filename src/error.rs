@@ -0,0 +1,134 @@
+//! Errors returned by fallible coin operations.
+
+use core::fmt;
+
+/// The error returned when a string can't be parsed into a [`Coin`](crate::Coin).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinParseError {
+    #[cfg(feature = "alloc")]
+    input: alloc::string::String,
+}
+
+impl CoinParseError {
+    pub(crate) fn new(input: &str) -> Self {
+        #[cfg(not(feature = "alloc"))]
+        let _ = input;
+
+        CoinParseError {
+            #[cfg(feature = "alloc")]
+            input: input.into(),
+        }
+    }
+
+    /// Returns the string that failed to parse.
+    #[cfg(feature = "alloc")]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl fmt::Display for CoinParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "alloc")]
+        return write!(f, "`{}` is not a valid coin", self.input);
+
+        #[cfg(not(feature = "alloc"))]
+        f.write_str("not a valid coin")
+    }
+}
+
+impl core::error::Error for CoinParseError {}
+
+/// The error type for fallible coin operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CoinError {
+    /// Exactly half of the coin's bits are set.
+    Ambiguous,
+    /// A string couldn't be parsed into a coin.
+    Parse(CoinParseError),
+    /// The coin has more bit flips than can be corrected.
+    Uncorrectable,
+}
+
+impl fmt::Display for CoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoinError::Ambiguous => f.write_str("coin is ambiguous, exactly half of its bits are set"),
+            CoinError::Parse(err) => err.fmt(f),
+            CoinError::Uncorrectable => f.write_str("coin has more bit flips than can be corrected"),
+        }
+    }
+}
+
+impl core::error::Error for CoinError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            CoinError::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<CoinParseError> for CoinError {
+    #[inline]
+    fn from(err: CoinParseError) -> Self {
+        CoinError::Parse(err)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::CoinError;
+
+    type Coin = crate::WideCoin<u8>;
+
+    #[test]
+    fn parse_error_reports_input() {
+        use super::CoinParseError;
+        use std::string::ToString;
+
+        let err = Coin::parse_flexible("maybe").unwrap_err();
+        let CoinError::Parse(parse_err) = &err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(parse_err.input(), "maybe");
+        assert_eq!(err.to_string(), "`maybe` is not a valid coin");
+        assert_eq!("yes".parse::<Coin>().unwrap_err(), CoinError::Parse(CoinParseError::new("yes")));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn coin_error_display() {
+        use std::string::ToString;
+
+        assert_eq!(
+            CoinError::Ambiguous.to_string(),
+            "coin is ambiguous, exactly half of its bits are set"
+        );
+        assert_eq!(
+            CoinError::Uncorrectable.to_string(),
+            "coin has more bit flips than can be corrected"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn coin_error_as_trait_object() {
+        use std::boxed::Box;
+        use std::error::Error;
+
+        fn parse_and_decode(s: &str) -> Result<bool, Box<dyn Error>> {
+            let coin: Coin = s.parse()?;
+            Ok(Coin::from_bits(coin.as_bits() & 0b0011_1100).try_to_bool()?)
+        }
+
+        let err = parse_and_decode("true").unwrap_err();
+        assert_eq!(err.downcast_ref::<CoinError>(), Some(&CoinError::Ambiguous));
+        assert!(err.source().is_none());
+
+        let err = parse_and_decode("nope").unwrap_err();
+        assert!(matches!(err.downcast_ref::<CoinError>(), Some(CoinError::Parse(_))));
+        assert!(err.source().is_some());
+    }
+}
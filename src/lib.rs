@@ -1,185 +1,120 @@
-use std::cell::Cell;
-use std::{convert::From, hash::Hash};
-// TODO: impl Deref to improve ergonomics
+#![no_std]
 
-/// A bit flip resistant Boolean type
-///
-/// Prefer `Coin` to `bool` in safety-critical environments with long-lived
-/// variables, such as global variables. `Coin` uses the same space as `bool`.
-///
-/// `Coin` imposes a small runtime and moderate ergonomic costs when being
-/// used in places where you would normally use `bool`. Therefore, you'll
-/// generally convert it to a standard `bool` as a local variable.
-///
-/// ## Examples
-///
-/// To use `Coin` in an `if` expression, it must first be converted to `bool`.
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use alloc::vec as __vec;
+
+/// Creates a [`Coin`], or a `Vec` of coins from a list.
 ///
-/// ```
-/// # use coin::Coin;
-/// # fn main() {
-/// let c = Coin::from(true);
+/// A single value expands to [`Coin::new`], so it can be used in `const`
+/// contexts. The list form requires the `alloc` feature.
 ///
-/// if c.to_bool() {
-///     println!("Clunky, but effective.");
-/// }
-/// # }
 /// ```
+/// # use coin::coin;
+/// let armed = const { coin!(true) };
+/// assert!(armed.to_bool());
 ///
-/// ## Warnings
-///
-/// Rust's `true` is converted to numeric types, such as `i32`, it becomes 1.
-/// This bit pattern (`0b0000_0001`) is considered to be `false` within the
-/// internal representation that's used by `Coin`.
-///
-/// ```
-/// # use coin::Coin;
-/// # fn main() {
-/// let c = Coin::from(true);
-/// let one = i32::from(c.to_bool());
-/// assert_eq!(Coin::from(one == 1), c);
+/// # #[cfg(feature = "alloc")] {
+/// let flags = coin![true, false, true];
+/// assert_eq!(flags.len(), 3);
+/// assert!(!flags[1].to_bool());
 /// # }
 /// ```
 ///
-/// ## Background and implementation notes
-///
-/// A standard `bool` is truth-biased, because `false` matches a single
-/// bit pattern (all zeros). A single bit flip invalidates the value.
-///
-/// `Coin` counts the number of bits to determine its truth value. When
-/// 4 or more bits are 1, the value is interpreted as `true`. `Coin` can
-/// tolerate 3 bit flips per byte before an incorrect value is returned.
-///
-/// For a more thorough introduction, see the talk "Software Security in the Presence of
-/// Faults" by Peter Gutmann (PDF <https://www.cs.auckland.ac.nz/~pgut001/pubs/software_faults.pdf>)
-/// (talk recording <https://www.youtube.com/watch?v=z0C7ymx5Jtk>).
-#[derive(Debug, Clone)]
-pub struct Coin(Cell<u8>);
-
-impl Coin {
-    #[inline]
-    fn truthy() -> Self {
-        Coin(Cell::new(u8::MAX))
-    }
-
-    #[inline]
-    fn falsey() -> Self {
-        Coin(Cell::new(u8::MIN))
-    }
-
-    #[inline(always)]
-    pub fn to_bool(&self) -> bool {
-        let val = self.0.get();
-        val.count_ones() >= val.count_zeros() // call twice to avoid baking a constant (4) into the binary
-        // TODO: what if a bit in the opcode flips?
-    }
-
-    fn degauss(&self) {
-        // TODO: what if bits in these constants accumulate errors?
-        let fresh_bits = match self.to_bool() {
-            true => u8::MAX,
-            false => u8::MIN,
-        };
-
-        self.0.set(fresh_bits);
-    }
-}
-
-impl Hash for Coin {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.degauss();
-        self.to_bool().hash(state);
-    }
+/// Note that `coin![true]` is a single coin, not a `Vec` of one.
+#[macro_export]
+macro_rules! coin {
+    ($b:expr $(,)?) => {
+        $crate::Coin::new($b)
+    };
+    ($($b:expr),+ $(,)?) => {
+        $crate::__vec![$($crate::Coin::new($b)),+]
+    };
 }
 
-impl Eq for Coin {}
-
-impl PartialEq for Coin {
-    #[inline(always)]
-    fn eq(&self, other: &Self) -> bool {
-        self.degauss();
-        other.degauss();
-        self.to_bool() == other.to_bool()
-    }
-}
-
-impl Ord for Coin {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.degauss();
-        other.degauss();
-        self.to_bool().cmp(&other.to_bool())
-    }
-}
-
-impl PartialOrd for Coin {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.degauss();
-        other.degauss();
-        self.to_bool().partial_cmp(&other.to_bool())
-    }
-}
-
-impl From<&Coin> for bool {
-    #[inline(always)]
-    fn from(c: &Coin) -> Self {
-        c.to_bool()
-    }
-}
-
-impl From<Coin> for bool {
-    #[inline(always)]
-    fn from(c: Coin) -> Self {
-        c.to_bool()
-    }
+macro_rules! impl_bool_like {
+    ($($t:ty),*) => {
+        $(
+            impl $crate::BoolLike for $t {
+                #[inline(always)]
+                fn truth(&self) -> bool {
+                    self.to_bool()
+                }
+            }
+        )*
+    };
 }
 
-impl From<bool> for Coin {
-    #[inline(always)]
-    fn from(b: bool) -> Self {
-        match b {
-            true  => Coin::truthy(),
-            false => Coin::falsey(),
-        }
-    }
-}
-
-
-#[cfg(test)]
-mod tests {
-    use super::Coin;
-
-    #[test]
-    fn one_bit_flip() {
-        let coin = Coin::from(true);
-        coin.0.set(0b1111_1011);
-        assert!(coin.to_bool());
-    }
-
-    #[test]
-    fn two_bits_flipped() {
-        let coin = Coin::from(true);
-        coin.0.set(0b1101_0011);
-        assert!(coin.to_bool());
-    }
-
-    #[test]
-    fn three_bits_flipped() {
-        let coin = Coin::from(true);
-        coin.0.set(0b1101_0011);
-        assert!(coin.to_bool());
-    }
-
-    #[test]
-    fn four_bits_flipped() {
-        let coin = Coin::from(true);
-        coin.0.set(0b1100_0011);
-        assert!(coin.to_bool());
-    }
-
-    #[test]
-    fn five_bits_flipped() {
-        let coin = Coin::from(true);
-        coin.0.set(0b1000_0011);
-        assert!(!coin.to_bool());
+#[cfg(target_has_atomic = "8")]
+mod atomic;
+pub mod encoding;
+mod error;
+mod monitor;
+mod option;
+mod packed;
+mod redundant;
+mod single;
+#[cfg(feature = "serde")]
+mod wire;
+mod word;
+
+#[cfg(target_has_atomic = "8")]
+pub use atomic::{scrub_all_atomic, AtomicCoin};
+pub use encoding::{Coin7, GrayCoin, HammingCoin};
+pub use error::{CoinError, CoinParseError};
+#[cfg(feature = "alloc")]
+pub use monitor::ScrubSchedule;
+pub use monitor::{CountingCoin, StuckBitDetector};
+pub use option::CoinOption;
+#[cfg(feature = "alloc")]
+pub use packed::{CoinBitmap, CoinBitset};
+pub use packed::{CoinFlags, Octet};
+pub use redundant::{DoubleCoin, RsCoin, ShadowCoin, TripleCoin};
+#[cfg(feature = "alloc")]
+pub use redundant::RedundantBool;
+pub use single::{all, any, scrub_all, BoolLike, Coin, CoinSnapshot, Decode, WideCoin};
+#[cfg(feature = "alloc")]
+pub use single::{from_bools, to_bools};
+#[cfg(feature = "bitvec")]
+pub use single::{from_bitslice, to_bitvec};
+#[cfg(feature = "serde")]
+pub use wire::{StrictCoin, TolerantCoin};
+pub use word::Word;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod wasm_tests {
+    use super::WideCoin;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    // Pinned, so that the byte patterns below hold under any `width-*`
+    // feature.
+    type Coin = WideCoin<u8>;
+
+    #[wasm_bindgen_test]
+    fn decodes_on_wasm() {
+        assert!(Coin::from(true).to_bool());
+        assert!(!Coin::from(false).to_bool());
+        assert!(Coin::from_bits(0b1101_1011).to_bool());
+        assert!(!WideCoin::<u64>::from_bits(0x7fff_ffff).to_bool());
+    }
+
+    #[wasm_bindgen_test]
+    fn converts_on_wasm() {
+        assert!(bool::from(Coin::from(true)));
+        assert_eq!(u8::from(Coin::from_bits(0b0000_0110)), u8::MIN);
+        assert_eq!("1".parse::<Coin>().unwrap(), Coin::from(true));
+    }
+
+    #[cfg(feature = "serde")]
+    #[wasm_bindgen_test]
+    fn serializes_to_js_booleans() {
+        assert_eq!(serde_json::to_string(&Coin::from(true)).unwrap(), "true");
+        assert_eq!(serde_json::to_string(&Coin::from_bits(0b0000_0001)).unwrap(), "false");
     }
 }
@@ -0,0 +1,282 @@
+//! Helpers for keeping long-lived coins healthy.
+
+use core::cell::Cell;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::Word;
+use crate::WideCoin;
+
+/// Watches a coin for bits that stay wrong across repeated scrubs
+///
+/// Radiation can permanently stick a bit at 0 or 1, and scrubbing can't
+/// repair that. Each call to [`StuckBitDetector::scrub`] records which bit
+/// positions disagreed with the canonical pattern. A position that has been
+/// wrong on `threshold` consecutive scrubs is reported as stuck.
+#[derive(Debug, Clone)]
+pub struct StuckBitDetector {
+    coin: WideCoin<u8>,
+    streaks: [Cell<u8>; 8],
+    threshold: u8,
+}
+
+impl StuckBitDetector {
+    /// Watches `coin`. A `threshold` of 0 is treated as 1.
+    pub fn new(coin: WideCoin<u8>, threshold: u8) -> Self {
+        StuckBitDetector {
+            coin,
+            streaks: Default::default(),
+            threshold: threshold.max(1),
+        }
+    }
+
+    /// Returns the coin that is being watched.
+    #[inline]
+    pub fn coin(&self) -> &WideCoin<u8> {
+        &self.coin
+    }
+
+    /// Scrubs the coin and records which bit positions were wrong.
+    pub fn scrub(&self) {
+        let before = self.coin.as_bits();
+        self.coin.scrub();
+        let wrong = before ^ self.coin.as_bits();
+
+        for (position, streak) in self.streaks.iter().enumerate() {
+            match wrong & (1 << position) != 0 {
+                true => streak.set(streak.get().saturating_add(1)),
+                false => streak.set(0),
+            }
+        }
+    }
+
+    /// Returns a mask of the bit positions that are considered stuck.
+    pub fn stuck_mask(&self) -> u8 {
+        self.streaks
+            .iter()
+            .enumerate()
+            .filter(|(_, streak)| streak.get() >= self.threshold)
+            .fold(0, |mask, (position, _)| mask | (1 << position))
+    }
+}
+
+/// Scrubs coins on a schedule driven by an external clock
+///
+/// Intended for `no_std` targets that have a monotonic tick but no threads.
+/// Call [`ScrubSchedule::tick`] from the main loop or a timer interrupt, and
+/// each coin is scrubbed once `interval` ticks have passed since it was last
+/// scrubbed. Every coin starts out as last scrubbed at tick 0.
+///
+/// ```
+/// # use coin::{Coin, ScrubSchedule};
+/// let coins = [Coin::from(true), Coin::from(false)];
+/// let mut schedule = ScrubSchedule::new(&coins, 100);
+///
+/// assert_eq!(schedule.tick(50), 0);
+/// assert_eq!(schedule.tick(100), 2);
+/// assert_eq!(schedule.tick(150), 0);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct ScrubSchedule<'a, W: Word> {
+    coins: &'a [WideCoin<W>],
+    last_scrub: Vec<u64>,
+    interval: u64,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, W: Word> ScrubSchedule<'a, W> {
+    pub fn new(coins: &'a [WideCoin<W>], interval: u64) -> Self {
+        ScrubSchedule {
+            coins,
+            last_scrub: alloc::vec![0; coins.len()],
+            interval,
+        }
+    }
+
+    /// Scrubs every coin that is due at tick `now`, returning how many were
+    /// scrubbed.
+    pub fn tick(&mut self, now: u64) -> usize {
+        let mut scrubbed = 0;
+        for (coin, last) in self.coins.iter().zip(&mut self.last_scrub) {
+            if now.saturating_sub(*last) >= self.interval {
+                coin.scrub();
+                *last = now;
+                scrubbed += 1;
+            }
+        }
+
+        scrubbed
+    }
+}
+
+/// A coin that counts the bits it has corrected over its lifetime
+///
+/// Each call to [`CountingCoin::scrub`] adds the number of bits it rewrote
+/// to a running total, which is useful for reliability reporting. The total
+/// saturates at `u16::MAX` rather than wrapping.
+///
+/// ```
+/// # use coin::CountingCoin;
+/// let coin = CountingCoin::new(true);
+/// coin.coin().flip_bit(2);
+/// coin.scrub();
+/// assert_eq!(coin.total_corrections(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CountingCoin {
+    coin: WideCoin<u8>,
+    corrections: Cell<u16>,
+}
+
+impl CountingCoin {
+    pub const fn new(b: bool) -> Self {
+        CountingCoin {
+            coin: WideCoin::new(b),
+            corrections: Cell::new(0),
+        }
+    }
+
+    /// Returns the underlying coin.
+    #[inline]
+    pub fn coin(&self) -> &WideCoin<u8> {
+        &self.coin
+    }
+
+    #[inline(always)]
+    pub fn to_bool(&self) -> bool {
+        self.coin.to_bool()
+    }
+
+    /// Rewrites the canonical bit pattern for the current value, and adds
+    /// the number of bits that changed to the total.
+    pub fn scrub(&self) {
+        // A byte can't have more than 8 bits corrected.
+        let fixed = self.coin.scrub_reporting() as u16;
+        self.corrections.set(self.corrections.get().saturating_add(fixed));
+    }
+
+    /// Returns the number of bits that scrubbing has corrected so far.
+    #[inline]
+    pub fn total_corrections(&self) -> u16 {
+        self.corrections.get()
+    }
+}
+
+impl From<bool> for CountingCoin {
+    #[inline(always)]
+    fn from(b: bool) -> Self {
+        CountingCoin::new(b)
+    }
+}
+
+impl_bool_like!(CountingCoin);
+
+#[cfg(test)]
+mod tests {
+    use super::{CountingCoin, StuckBitDetector};
+
+    type Coin = crate::WideCoin<u8>;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn scrub_schedule() {
+        use super::ScrubSchedule;
+
+        let coins = [Coin::from(true), Coin::from(true), Coin::from(false)];
+        let mut schedule = ScrubSchedule::new(&coins, 10);
+        // Stagger the coins, as if they had been scrubbed at ticks 0, 4 and 7.
+        schedule.last_scrub.copy_from_slice(&[0, 4, 7]);
+
+        coins[0].flip_bit(3);
+        coins[1].flip_bit(6);
+        coins[2].flip_bit(0);
+        coins[2].flip_bit(1);
+
+        assert_eq!(schedule.tick(9), 0);
+        assert_eq!(schedule.tick(10), 1);
+        assert_eq!(coins[0].as_bits(), u8::MAX);
+        assert_eq!(coins[1].as_bits(), 0b1011_1111);
+
+        assert_eq!(schedule.tick(14), 1);
+        assert_eq!(coins[1].as_bits(), u8::MAX);
+        assert_eq!(coins[2].as_bits(), 0b0000_0011);
+
+        assert_eq!(schedule.tick(16), 0);
+        assert_eq!(schedule.tick(17), 1);
+        assert_eq!(coins[2].as_bits(), u8::MIN);
+
+        assert_eq!(schedule.last_scrub, [10, 14, 17]);
+        assert_eq!(schedule.tick(40), 3);
+    }
+
+    #[test]
+    fn stuck_bit_detector_reports_a_stuck_bit() {
+        let detector = StuckBitDetector::new(Coin::from(true), 3);
+        let stick = || detector.coin().flip_bit(5);
+
+        for _ in 0..2 {
+            stick();
+            detector.scrub();
+            assert_eq!(detector.stuck_mask(), 0);
+        }
+
+        stick();
+        detector.scrub();
+        assert_eq!(detector.stuck_mask(), 1 << 5);
+    }
+
+    #[test]
+    fn stuck_bit_detector_forgets_transient_flips() {
+        let detector = StuckBitDetector::new(Coin::from(false), 2);
+
+        detector.coin().flip_bit(0);
+        detector.scrub();
+        detector.coin().flip_bit(1);
+        detector.scrub();
+        assert_eq!(detector.stuck_mask(), 0);
+
+        detector.coin().flip_bit(1);
+        detector.scrub();
+        assert_eq!(detector.stuck_mask(), 0b0000_0010);
+
+        detector.scrub();
+        assert_eq!(detector.stuck_mask(), 0);
+    }
+
+    #[test]
+    fn counting_coin_accumulates_corrections() {
+        let coin = CountingCoin::from(true);
+        coin.scrub();
+        assert_eq!(coin.total_corrections(), 0);
+
+        for flips in [[1, 5, 6], [0, 0, 7], [2, 3, 4]] {
+            for bit in flips {
+                coin.coin().flip_bit(bit);
+            }
+            coin.scrub();
+        }
+        // Flipping bit 0 twice leaves it correct.
+        assert_eq!(coin.total_corrections(), 3 + 1 + 3);
+        assert!(coin.to_bool());
+    }
+
+    #[test]
+    fn counting_coin_saturates() {
+        let coin = CountingCoin::from(false);
+        coin.corrections.set(u16::MAX - 2);
+
+        coin.coin().flip_bit(0);
+        coin.scrub();
+        assert_eq!(coin.total_corrections(), u16::MAX - 1);
+
+        for _ in 0..3 {
+            coin.coin().flip_bit(4);
+            coin.coin().flip_bit(5);
+            coin.scrub();
+            assert_eq!(coin.total_corrections(), u16::MAX);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! An optional value whose presence is stored in a coin.
+
+use core::cell::Cell;
+
+use crate::WideCoin;
+
+/// An optional value whose discriminant survives bit flips
+///
+/// `Option<T>` records whether a value is present in a discriminant that a
+/// single bit flip can change. `CoinOption` keeps the value alongside a
+/// [`Coin`] presence flag instead, so the flag tolerates 3 bit flips. The
+/// value itself is not protected.
+///
+/// Like [`Cell`], a `CoinOption` can be updated through a shared reference,
+/// so `T` must be `Copy`, and [`CoinOption::get`] returns a copy.
+///
+/// ```
+/// # use coin::CoinOption;
+/// let deadline = CoinOption::<u32>::none();
+/// assert_eq!(deadline.get(), None);
+///
+/// deadline.set(1_500);
+/// assert_eq!(deadline.get(), Some(1_500));
+///
+/// deadline.clear();
+/// assert_eq!(deadline.get(), None);
+/// ```
+///
+/// [`Coin`]: crate::Coin
+#[derive(Debug, Clone)]
+pub struct CoinOption<T: Copy> {
+    present: WideCoin<u8>,
+    value: Cell<T>,
+}
+
+impl<T: Copy> CoinOption<T> {
+    /// Creates a `CoinOption` holding `value`.
+    pub const fn some(value: T) -> Self {
+        CoinOption {
+            present: WideCoin::new(true),
+            value: Cell::new(value),
+        }
+    }
+
+    /// Returns the value if the presence flag decodes as `true`.
+    #[inline]
+    pub fn get(&self) -> Option<T> {
+        match self.present.to_bool() {
+            true => Some(self.value.get()),
+            false => None,
+        }
+    }
+
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.present.to_bool()
+    }
+
+    /// Stores `value` and marks it present.
+    #[inline]
+    pub fn set(&self, value: T) {
+        self.value.set(value);
+        self.present.set(true);
+    }
+
+    /// Marks the value absent. The stored value is left in place, but
+    /// [`CoinOption::get`] no longer returns it.
+    #[inline]
+    pub fn clear(&self) {
+        self.present.set(false);
+    }
+
+    /// Rewrites the canonical bit pattern of the presence flag.
+    #[inline]
+    pub fn scrub(&self) {
+        self.present.scrub();
+    }
+}
+
+impl<T: Copy + Default> CoinOption<T> {
+    /// Creates a `CoinOption` with no value. `T::default()` fills the unused
+    /// storage.
+    pub fn none() -> Self {
+        CoinOption {
+            present: WideCoin::new(false),
+            value: Cell::new(T::default()),
+        }
+    }
+}
+
+impl<T: Copy + Default> Default for CoinOption<T> {
+    fn default() -> Self {
+        CoinOption::none()
+    }
+}
+
+impl<T: Copy + Default> From<Option<T>> for CoinOption<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => CoinOption::some(value),
+            None => CoinOption::none(),
+        }
+    }
+}
+
+impl<T: Copy> From<CoinOption<T>> for Option<T> {
+    #[inline]
+    fn from(option: CoinOption<T>) -> Self {
+        option.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CoinOption;
+
+    #[test]
+    fn set_and_clear() {
+        let option = CoinOption::from(Some('x'));
+        assert_eq!(option.get(), Some('x'));
+        assert!(option.is_some());
+
+        option.clear();
+        assert_eq!(option.get(), None);
+        assert!(!option.is_some());
+
+        option.set('y');
+        assert_eq!(Option::from(option), Some('y'));
+        assert_eq!(Option::<u8>::from(CoinOption::default()), None);
+    }
+
+    #[test]
+    fn presence_survives_flips() {
+        let option = CoinOption::some(7_u64);
+        for bit in [0, 3, 6] {
+            option.present.flip_bit(bit);
+        }
+        assert_eq!(option.get(), Some(7));
+        option.scrub();
+        assert!(option.present.is_canonical());
+
+        let option = CoinOption::<u64>::none();
+        for bit in [1, 2, 7] {
+            option.present.flip_bit(bit);
+        }
+        assert_eq!(option.get(), None);
+    }
+}
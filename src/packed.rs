@@ -0,0 +1,625 @@
+//! Several Boolean values stored side by side.
+
+use core::cell::Cell;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::word::{canonical, confidence, decode, swar};
+
+/// A growable array of bit flip resistant Boolean values
+///
+/// Each value occupies its own byte and is decoded with the same rule as
+/// [`Coin`], but the bytes are stored contiguously in a single `Vec<u8>`
+/// rather than as separate coins.
+///
+/// [`Coin`]: crate::Coin
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoinBitset(Vec<u8>);
+
+#[cfg(feature = "alloc")]
+impl CoinBitset {
+    pub const fn new() -> Self {
+        CoinBitset(Vec::new())
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Appends the canonical bit pattern for `b`.
+    pub fn push(&mut self, b: bool) {
+        self.0.push(canonical(b));
+    }
+
+    /// Decodes the value at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> bool {
+        decode(self.0[index])
+    }
+
+    /// Stores the canonical bit pattern for `b` at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn set(&mut self, index: usize, b: bool) {
+        self.0[index] = canonical(b);
+    }
+
+    /// Decodes every value in order.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        self.0.iter().map(|&bits| decode(bits))
+    }
+
+    /// Returns the raw bytes without decoding or scrubbing them.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Flips bit `bit` of the byte at `index`, simulating a fault.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, or if `bit` is not less than 8.
+    pub fn flip_bit(&mut self, index: usize, bit: u8) {
+        assert!(bit < 8, "bit index {bit} out of range for a byte");
+        self.0[index] ^= 1 << bit;
+    }
+
+    /// Rewrites the canonical bit pattern of every value.
+    pub fn scrub_all(&mut self) {
+        for bits in &mut self.0 {
+            *bits = canonical(decode(*bits));
+        }
+    }
+}
+
+/// A fixed-length mask of bit flip resistant Boolean values
+///
+/// Like [`CoinBitset`], each value occupies its own byte, but a
+/// `CoinBitmap` is built once from a mask of known length, and
+/// [`CoinBitmap::get`] returns `None` rather than panicking when asked for a
+/// value beyond it.
+///
+/// With the `serde` feature, a `CoinBitmap` is written as a byte string: the
+/// length followed by the canonical byte for each value. A corrupted byte is
+/// decoded before it is written. Reading a mask back rewrites the canonical
+/// pattern of every value.
+///
+/// ```
+/// # use coin::CoinBitmap;
+/// let mut mask = CoinBitmap::from_bool_slice(&[true, false, true]);
+/// mask.set(1, true);
+///
+/// assert_eq!(mask.len(), 3);
+/// assert_eq!(mask.get(1), Some(true));
+/// assert_eq!(mask.get(3), None);
+/// assert!(mask.iter().all(|b| b));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoinBitmap(CoinBitset);
+
+#[cfg(feature = "alloc")]
+impl CoinBitmap {
+    /// Creates a mask of `len` values, all `false`.
+    pub fn new(len: usize) -> Self {
+        CoinBitmap(CoinBitset(alloc::vec![canonical(false); len]))
+    }
+
+    /// Creates a mask holding the canonical bit pattern of each value in
+    /// `mask`.
+    pub fn from_bool_slice(mask: &[bool]) -> Self {
+        CoinBitmap::from(mask)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Decodes the value at `index`, or returns `None` if `index` is out of
+    /// bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<bool> {
+        (index < self.len()).then(|| self.0.get(index))
+    }
+
+    /// Stores the canonical bit pattern for `b` at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn set(&mut self, index: usize, b: bool) {
+        self.0.set(index, b);
+    }
+
+    /// Decodes every value in order.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        self.0.iter()
+    }
+
+    /// Returns the raw bytes without decoding or scrubbing them.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// Flips bit `bit` of the byte at `index`, simulating a fault.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, or if `bit` is not less than 8.
+    pub fn flip_bit(&mut self, index: usize, bit: u8) {
+        self.0.flip_bit(index, bit);
+    }
+
+    /// Rewrites the canonical bit pattern of every value.
+    pub fn scrub_all(&mut self) {
+        self.0.scrub_all();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&[bool]> for CoinBitmap {
+    fn from(mask: &[bool]) -> Self {
+        mask.iter().copied().collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromIterator<bool> for CoinBitmap {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        CoinBitmap(CoinBitset(iter.into_iter().map(canonical).collect()))
+    }
+}
+
+/// Writes the length and the canonical byte of each value as a byte string.
+#[cfg(all(feature = "alloc", feature = "serde"))]
+impl serde::Serialize for CoinBitmap {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes: Vec<u8> = self.iter().map(canonical).collect();
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+/// Reads a byte string, or a sequence of bytes, decoding each byte into a
+/// canonical value.
+#[cfg(all(feature = "alloc", feature = "serde"))]
+impl<'de> serde::Deserialize<'de> for CoinBitmap {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BitmapVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for BitmapVisitor {
+            type Value = CoinBitmap;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("a byte string of coins")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, bytes: &[u8]) -> Result<CoinBitmap, E> {
+                Ok(bytes.iter().map(|&bits| decode(bits)).collect())
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<CoinBitmap, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(bits) = seq.next_element::<u8>()? {
+                    bytes.push(canonical(decode(bits)));
+                }
+                Ok(CoinBitmap(CoinBitset(bytes)))
+            }
+        }
+
+        deserializer.deserialize_bytes(BitmapVisitor)
+    }
+}
+
+/// Eight bit flip resistant Boolean values packed into a single `u64`
+///
+/// Each value occupies one byte of the word and is decoded with the same
+/// rule as [`Coin`], so it tolerates 3 bit flips. Keeping the eight bytes
+/// in one word is more cache-friendly than eight separate coins, and lets
+/// [`Octet::scrub`] rewrite all of them at once.
+///
+/// Value `i` is stored in bits `8 * i` to `8 * i + 7`.
+///
+/// [`Coin`]: crate::Coin
+#[derive(Debug, Clone, Default)]
+pub struct Octet(Cell<u64>);
+
+impl Octet {
+    /// Creates an `Octet` with every value `false`.
+    pub const fn new() -> Self {
+        Octet(Cell::new(0))
+    }
+
+    #[inline(always)]
+    fn byte(&self, index: usize) -> u8 {
+        assert!(index < 8, "index {index} out of range for an Octet");
+        (self.0.get() >> (8 * index)) as u8
+    }
+
+    /// Decodes the value at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 8.
+    #[inline]
+    pub fn get(&self, index: usize) -> bool {
+        decode(self.byte(index))
+    }
+
+    /// Stores the canonical bit pattern for `b` at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 8.
+    #[inline]
+    pub fn set(&self, index: usize, b: bool) {
+        self.byte(index);
+        let shift = 8 * index;
+        let cleared = self.0.get() & !(0xff << shift);
+        self.0.set(cleared | (u64::from(canonical::<u8>(b)) << shift));
+    }
+
+    /// Returns how far the value at `index` is from the decision boundary,
+    /// as [`WideCoin::confidence`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 8.
+    ///
+    /// [`WideCoin::confidence`]: crate::WideCoin::confidence
+    #[inline]
+    pub fn confidence(&self, index: usize) -> u8 {
+        confidence(self.byte(index))
+    }
+
+    /// Returns the raw bits without decoding or scrubbing them.
+    #[inline]
+    pub fn as_bits(&self) -> u64 {
+        self.0.get()
+    }
+
+    /// Flips bit `index` of the raw bits, simulating a fault.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 64.
+    pub fn flip_bit(&self, index: u8) {
+        assert!(index < 64, "bit index {index} out of range for an Octet");
+        self.0.set(self.0.get() ^ (1 << index));
+    }
+
+    /// Rewrites the canonical bit pattern of all eight values.
+    pub fn scrub(&self) {
+        self.0.set(swar::canonical_bytes(self.0.get()));
+    }
+}
+
+impl From<[bool; 8]> for Octet {
+    fn from(values: [bool; 8]) -> Self {
+        let octet = Octet::new();
+        for (index, b) in values.into_iter().enumerate() {
+            octet.set(index, b);
+        }
+        octet
+    }
+}
+
+impl From<Octet> for [bool; 8] {
+    fn from(octet: Octet) -> Self {
+        core::array::from_fn(|index| octet.get(index))
+    }
+}
+
+/// A fixed set of `N` named options, each stored in its own byte
+///
+/// The `bitflags` crate packs one option into each bit, so a single flip
+/// changes the set. `CoinFlags` spends a byte per option and decodes each
+/// with the same rule as [`Coin`], so every option tolerates 3 bit flips.
+///
+/// Options are identified by index. Any type that converts into `usize`
+/// can be used as a key, such as an enum of option names.
+///
+/// ```
+/// # use coin::CoinFlags;
+/// #[derive(Clone, Copy)]
+/// enum Mode {
+///     Armed,
+///     Logging,
+///     Safe,
+/// }
+///
+/// impl From<Mode> for usize {
+///     fn from(mode: Mode) -> usize {
+///         mode as usize
+///     }
+/// }
+///
+/// let mut modes = CoinFlags::<3>::new();
+/// modes.insert(Mode::Armed);
+/// modes.insert(Mode::Safe);
+/// modes.remove(Mode::Armed);
+///
+/// assert!(!modes.contains(Mode::Armed));
+/// assert!(!modes.contains(Mode::Logging));
+/// assert!(modes.contains(Mode::Safe));
+/// ```
+///
+/// [`Coin`]: crate::Coin
+#[derive(Debug, Clone)]
+pub struct CoinFlags<const N: usize>([u8; N]);
+
+impl<const N: usize> CoinFlags<N> {
+    /// Creates a set with every option absent.
+    pub const fn new() -> Self {
+        CoinFlags([0; N])
+    }
+
+    /// Adds `flag` to the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `flag` is not less than `N`.
+    #[inline]
+    pub fn insert(&mut self, flag: impl Into<usize>) {
+        self.0[flag.into()] = canonical(true);
+    }
+
+    /// Removes `flag` from the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `flag` is not less than `N`.
+    #[inline]
+    pub fn remove(&mut self, flag: impl Into<usize>) {
+        self.0[flag.into()] = canonical(false);
+    }
+
+    /// Decodes whether `flag` is in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `flag` is not less than `N`.
+    #[inline]
+    pub fn contains(&self, flag: impl Into<usize>) -> bool {
+        decode(self.0[flag.into()])
+    }
+
+    /// Returns the raw bytes without decoding or scrubbing them.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    /// Flips bit `bit` of the byte for `flag`, simulating a fault.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `flag` is not less than `N`, or if `bit` is not less than 8.
+    pub fn flip_bit(&mut self, flag: impl Into<usize>, bit: u8) {
+        assert!(bit < 8, "bit index {bit} out of range for a byte");
+        self.0[flag.into()] ^= 1 << bit;
+    }
+
+    /// Rewrites the canonical bit pattern of every option.
+    pub fn scrub_all(&mut self) {
+        for bits in &mut self.0 {
+            *bits = canonical(decode(*bits));
+        }
+    }
+}
+
+impl<const N: usize> Default for CoinFlags<N> {
+    fn default() -> Self {
+        CoinFlags::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use super::{CoinBitmap, CoinBitset};
+    use super::{CoinFlags, Octet};
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn coin_bitset_round_trip() {
+        let pattern = [true, false, false, true, true, true, false, true, false];
+
+        let mut bitset = CoinBitset::new();
+        assert!(bitset.is_empty());
+        for b in pattern {
+            bitset.push(b);
+        }
+        assert_eq!(bitset.len(), pattern.len());
+
+        for (i, b) in pattern.into_iter().enumerate() {
+            assert_eq!(bitset.get(i), b);
+        }
+
+        bitset.set(1, true);
+        bitset.set(3, false);
+        assert!(bitset.get(1));
+        assert!(!bitset.get(3));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn coin_bitset_scrub_all() {
+        let mut bitset = CoinBitset::new();
+        for i in 0..64 {
+            bitset.push(i % 3 == 0);
+        }
+
+        let mut seed = 0x2545_f491_u32;
+        for _ in 0..40 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let index = (seed >> 8) as usize % bitset.len();
+            let bit = (seed >> 4) % 8;
+            // Only flip bytes that can absorb another flip.
+            let bits = bitset.as_bytes()[index];
+            if bits.count_ones().abs_diff(bits.count_zeros()) > 2 {
+                bitset.flip_bit(index, bit as u8);
+            }
+        }
+
+        bitset.scrub_all();
+        for i in 0..64 {
+            assert_eq!(bitset.as_bytes()[i], if i % 3 == 0 { u8::MAX } else { u8::MIN });
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    const MASK: [bool; 13] = [
+        true, false, false, true, true, true, false, true, false, false, true, false, true,
+    ];
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn coin_bitmap_get_set() {
+        let mut bitmap = CoinBitmap::from_bool_slice(&MASK);
+        assert_eq!(bitmap.len(), 13);
+        assert!(bitmap.iter().eq(MASK));
+
+        bitmap.set(0, false);
+        bitmap.set(12, false);
+        assert_eq!(bitmap.get(0), Some(false));
+        assert_eq!(bitmap.get(12), Some(false));
+
+        assert_eq!(bitmap.get(13), None);
+        assert_eq!(CoinBitmap::new(0).get(0), None);
+        assert!(CoinBitmap::new(4).iter().all(|b| !b));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn coin_bitmap_scrub_all() {
+        let mut bitmap = CoinBitmap::from(&MASK[..]);
+        // Leaves 0b1110_1001 in byte 3 and 0b0001_0100 in byte 8.
+        for (index, bit) in [(3, 1), (3, 2), (3, 4), (8, 2), (8, 4)] {
+            bitmap.flip_bit(index, bit);
+        }
+        assert!(bitmap.iter().eq(MASK));
+
+        bitmap.scrub_all();
+        assert_eq!(bitmap, CoinBitmap::from(&MASK[..]));
+    }
+
+    #[cfg(all(feature = "alloc", feature = "serde"))]
+    #[test]
+    fn coin_bitmap_serde_round_trip() {
+        let mut bitmap = CoinBitmap::from(&MASK[..]);
+        // Leaves 0b1011_0110 in byte 5.
+        for bit in [0, 3, 6] {
+            bitmap.flip_bit(5, bit);
+        }
+
+        let bytes = bincode::serialize(&bitmap).unwrap();
+        assert_eq!(bytes.len(), 8 + 13, "a length followed by one byte per value");
+        assert_eq!(bytes[8 + 5], u8::MAX);
+
+        let decoded: CoinBitmap = bincode::deserialize(&bytes).unwrap();
+        assert!(decoded.iter().eq(MASK));
+        assert_eq!(decoded.as_bytes()[5], u8::MAX);
+
+        let json = serde_json::to_string(&bitmap).unwrap();
+        let decoded: CoinBitmap = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, CoinBitmap::from(&MASK[..]));
+    }
+
+    #[test]
+    fn octet_get_set() {
+        let pattern = [true, false, false, true, true, false, true, false];
+        let octet = Octet::from(pattern);
+        assert_eq!(octet.as_bits(), 0x00ff_00ff_ff00_00ff);
+        for (i, b) in pattern.into_iter().enumerate() {
+            assert_eq!(octet.get(i), b);
+        }
+
+        octet.set(1, true);
+        octet.set(3, false);
+        assert!(octet.get(1));
+        assert!(!octet.get(3));
+        assert!(octet.get(0) && octet.get(4));
+    }
+
+    #[test]
+    fn octet_corruption_is_confined_to_one_flag() {
+        let pattern = [true, false, false, true, true, false, true, false];
+        let octet = Octet::from(pattern);
+
+        // Flip 3 bits of flag 5's byte.
+        for bit in [42, 43, 45] {
+            octet.flip_bit(bit);
+        }
+        assert_eq!(<[bool; 8]>::from(octet.clone()), pattern);
+        for i in 0..8 {
+            assert_eq!(octet.confidence(i), if i == 5 { 2 } else { 8 }, "flag {i}");
+        }
+
+        octet.scrub();
+        assert_eq!(octet.as_bits(), 0x00ff_00ff_ff00_00ff);
+        assert!((0..8).all(|i| octet.confidence(i) == 8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn octet_index_out_of_range() {
+        Octet::new().get(8);
+    }
+
+    #[test]
+    fn coin_flags_survive_a_corrupted_byte() {
+        const ARMED: usize = 0;
+        const LOGGING: usize = 1;
+        const SAFE: usize = 2;
+
+        let mut flags = CoinFlags::<3>::default();
+        flags.insert(ARMED);
+        flags.insert(LOGGING);
+        flags.insert(SAFE);
+        flags.remove(LOGGING);
+
+        // Leaves 0b1101_0110 for ARMED and 0b0010_0001 for LOGGING.
+        for bit in [0, 3, 5] {
+            flags.flip_bit(ARMED, bit);
+        }
+        for bit in [0, 5] {
+            flags.flip_bit(LOGGING, bit);
+        }
+        assert!(flags.contains(ARMED));
+        assert!(!flags.contains(LOGGING));
+        assert!(flags.contains(SAFE));
+
+        flags.scrub_all();
+        assert_eq!(flags.as_bytes(), &[u8::MAX, u8::MIN, u8::MAX]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn coin_flags_out_of_range() {
+        CoinFlags::<2>::new().contains(2usize);
+    }
+}
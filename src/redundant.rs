@@ -0,0 +1,541 @@
+//! Boolean types that keep more than one copy of their value.
+
+use core::cell::Cell;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::error::CoinError;
+use crate::word::{canonical, confidence, decode, Word};
+use crate::WideCoin;
+
+/// A coin padded out to fill its own cache line.
+#[derive(Debug, Clone)]
+#[repr(align(64))]
+struct Isolated(WideCoin<u8>);
+
+/// A bit flip resistant Boolean type with triple modular redundancy
+///
+/// A particle strike can corrupt many bits of a single memory location at
+/// once, which may be more than one [`Coin`] can tolerate. `TripleCoin`
+/// keeps three independent copies of its value, each aligned to its own
+/// 64-byte cache line, and decodes by majority vote. Any one copy can be
+/// lost entirely without affecting the result.
+///
+/// The price is space: a `TripleCoin` occupies 192 bytes.
+///
+/// [`Coin`]: crate::Coin
+#[derive(Debug, Clone)]
+pub struct TripleCoin([Isolated; 3]);
+
+impl TripleCoin {
+    pub const fn new(b: bool) -> Self {
+        TripleCoin([
+            Isolated(WideCoin::new(b)),
+            Isolated(WideCoin::new(b)),
+            Isolated(WideCoin::new(b)),
+        ])
+    }
+
+    /// Decodes each copy, then returns the majority value.
+    #[inline]
+    pub fn to_bool(&self) -> bool {
+        let votes = self.0.iter().filter(|copy| copy.0.to_bool()).count();
+        votes >= 2
+    }
+
+    /// Rewrites the canonical bit pattern of the majority value to every copy.
+    pub fn scrub(&self) {
+        let b = self.to_bool();
+        for copy in &self.0 {
+            copy.0.set(b);
+        }
+    }
+}
+
+impl From<bool> for TripleCoin {
+    #[inline(always)]
+    fn from(b: bool) -> Self {
+        TripleCoin::new(b)
+    }
+}
+
+impl From<TripleCoin> for bool {
+    #[inline(always)]
+    fn from(c: TripleCoin) -> Self {
+        c.to_bool()
+    }
+}
+
+/// A bit flip resistant Boolean type made of two bytes that cross-check
+///
+/// `DoubleCoin` sits between [`Coin`] and [`TripleCoin`]. Each byte is
+/// decoded with the same rule as `Coin`. When the two disagree, the one
+/// further from the decision boundary wins, and the disagreement is recorded
+/// so that it can be reported.
+///
+/// [`Coin`]: crate::Coin
+#[derive(Debug, Clone)]
+pub struct DoubleCoin {
+    first: Cell<u8>,
+    second: Cell<u8>,
+    disagreed: Cell<bool>,
+}
+
+impl DoubleCoin {
+    pub const fn new(b: bool) -> Self {
+        let bits = match b {
+            true => u8::MAX,
+            false => u8::MIN,
+        };
+
+        DoubleCoin {
+            first: Cell::new(bits),
+            second: Cell::new(bits),
+            disagreed: Cell::new(false),
+        }
+    }
+
+    /// Decodes both bytes, returning the more confident value when they
+    /// disagree. The first byte wins when they are equally confident.
+    #[inline]
+    pub fn to_bool(&self) -> bool {
+        let (first, second) = (self.first.get(), self.second.get());
+        let (a, b) = (decode(first), decode(second));
+        self.disagreed.set(a != b);
+
+        match a == b || confidence(first) >= confidence(second) {
+            true => a,
+            false => b,
+        }
+    }
+
+    /// Returns `true` if the two bytes decoded differently during the most
+    /// recent call to [`DoubleCoin::to_bool`] or [`DoubleCoin::scrub`].
+    #[inline]
+    pub fn disagreed(&self) -> bool {
+        self.disagreed.get()
+    }
+
+    /// Rewrites the canonical bit pattern for the current value to both
+    /// bytes.
+    pub fn scrub(&self) {
+        let fresh_bits = canonical(self.to_bool());
+        self.first.set(fresh_bits);
+        self.second.set(fresh_bits);
+    }
+}
+
+impl From<bool> for DoubleCoin {
+    #[inline(always)]
+    fn from(b: bool) -> Self {
+        DoubleCoin::new(b)
+    }
+}
+
+impl From<DoubleCoin> for bool {
+    #[inline(always)]
+    fn from(c: DoubleCoin) -> Self {
+        c.to_bool()
+    }
+}
+
+/// A bit flip resistant Boolean type with a last known good copy
+///
+/// `ShadowCoin` stores its value twice, in a primary byte and a shadow
+/// byte, each decoded with the same rule as [`Coin`]. Reads trust whichever
+/// byte is further from the decision boundary, so a primary that has been
+/// corrupted past its threshold is outvoted by a healthy shadow.
+///
+/// [`Coin`]: crate::Coin
+#[derive(Debug, Clone)]
+pub struct ShadowCoin {
+    primary: Cell<u8>,
+    shadow: Cell<u8>,
+}
+
+impl ShadowCoin {
+    pub const fn new(b: bool) -> Self {
+        let bits = match b {
+            true => u8::MAX,
+            false => u8::MIN,
+        };
+
+        ShadowCoin {
+            primary: Cell::new(bits),
+            shadow: Cell::new(bits),
+        }
+    }
+
+    /// Decodes the more confident of the two bytes, preferring the primary
+    /// when they are equally confident.
+    #[inline]
+    pub fn to_bool(&self) -> bool {
+        let (primary, shadow) = (self.primary.get(), self.shadow.get());
+        match confidence(shadow) > confidence(primary) {
+            true => decode(shadow),
+            false => decode(primary),
+        }
+    }
+
+    /// Rewrites the canonical bit pattern for the current value to both
+    /// bytes.
+    pub fn scrub(&self) {
+        let fresh_bits = canonical(self.to_bool());
+        self.primary.set(fresh_bits);
+        self.shadow.set(fresh_bits);
+    }
+}
+
+impl From<bool> for ShadowCoin {
+    #[inline(always)]
+    fn from(b: bool) -> Self {
+        ShadowCoin::new(b)
+    }
+}
+
+impl From<ShadowCoin> for bool {
+    #[inline(always)]
+    fn from(c: ShadowCoin) -> Self {
+        c.to_bool()
+    }
+}
+
+/// A bit flip resistant Boolean type protected by a Reed–Solomon code
+///
+/// Counting bits, as [`Coin`] does, copes with scattered flips but not with
+/// a burst that wipes out a whole byte. `RsCoin` treats each of its five
+/// bytes as a symbol of a Reed–Solomon code over GF(2⁸) with one data
+/// symbol and four check symbols, RS(5, 1). With a single data symbol every
+/// check symbol equals the data symbol, so this RS(5, 1) code is simply five
+/// copies of one byte, `0xFF` for `true` and `0x00` for `false`. Encoding is
+/// a copy and decoding needs no field arithmetic.
+///
+/// The code has a minimum distance of 5 symbols, so any 2 corrupted bytes
+/// are corrected, however many of their bits have flipped. Past 2 corrupted
+/// bytes there is no guarantee: [`RsCoin::try_to_bool`] may return
+/// [`CoinError::Uncorrectable`], or it may return a confidently wrong `Ok`.
+/// Three zeroed bytes of a `true` coin, for instance, are indistinguishable
+/// from two damaged bytes of a `false` one, and decode as `Ok(false)`.
+///
+/// [`Coin`]: crate::Coin
+#[derive(Debug, Clone)]
+pub struct RsCoin([Cell<u8>; 5]);
+
+impl RsCoin {
+    // The number of corrupted symbols that can be corrected, `(5 - 1) / 2`.
+    const CAPACITY: usize = 2;
+
+    pub const fn new(b: bool) -> Self {
+        let bits = match b {
+            true => u8::MAX,
+            false => u8::MIN,
+        };
+
+        RsCoin([
+            Cell::new(bits),
+            Cell::new(bits),
+            Cell::new(bits),
+            Cell::new(bits),
+            Cell::new(bits),
+        ])
+    }
+
+    /// Decodes the coin, correcting up to 2 corrupted bytes.
+    ///
+    /// Returns [`CoinError::Uncorrectable`] when the bytes are not within 2
+    /// symbols of either codeword. With 3 or more corrupted bytes, the bytes
+    /// may instead lie within 2 symbols of the wrong codeword, which is
+    /// returned as `Ok`.
+    pub fn try_to_bool(&self) -> Result<bool, CoinError> {
+        let corrupted = |symbol: u8| self.0.iter().filter(|bits| bits.get() != symbol).count();
+
+        if corrupted(canonical(true)) <= Self::CAPACITY {
+            Ok(true)
+        } else if corrupted(canonical(false)) <= Self::CAPACITY {
+            Ok(false)
+        } else {
+            Err(CoinError::Uncorrectable)
+        }
+    }
+
+    /// Decodes the coin.
+    ///
+    /// When the bytes can't be corrected, this falls back to counting all
+    /// 40 bits in the same way as [`Coin`].
+    ///
+    /// [`Coin`]: crate::Coin
+    #[inline]
+    pub fn to_bool(&self) -> bool {
+        self.try_to_bool().unwrap_or_else(|_| {
+            let ones: u32 = self.0.iter().map(|bits| Word::count_ones(bits.get())).sum();
+            ones >= 40 - ones
+        })
+    }
+
+    /// Rewrites the codeword for the current value to every byte.
+    pub fn scrub(&self) {
+        let fresh_bits = canonical(self.to_bool());
+        for bits in &self.0 {
+            bits.set(fresh_bits);
+        }
+    }
+}
+
+impl From<bool> for RsCoin {
+    #[inline(always)]
+    fn from(b: bool) -> Self {
+        RsCoin::new(b)
+    }
+}
+
+impl From<RsCoin> for bool {
+    #[inline(always)]
+    fn from(c: RsCoin) -> Self {
+        c.to_bool()
+    }
+}
+
+/// A configurable number of copies of the same value, decoded by quorum
+///
+/// [`TripleCoin`] fixes the number of copies at three. `RedundantBool` keeps
+/// as many as it's created with, each a separate [`Coin`], and decodes to the
+/// value that a majority of the copies decode to. A tie, only possible with
+/// an even number of copies, resolves to `true`, as [`WideCoin::vote`] does.
+///
+/// With `n` copies, up to `(n - 1) / 2` copies can be lost entirely without
+/// affecting the result.
+///
+/// ```
+/// # use coin::RedundantBool;
+/// let armed = RedundantBool::new(5);
+/// armed.set(true);
+/// assert!(armed.get());
+/// assert_eq!(armed.healthy_copies(), 5);
+/// ```
+///
+/// [`Coin`]: crate::Coin
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct RedundantBool(Vec<WideCoin<u8>>);
+
+#[cfg(feature = "alloc")]
+impl RedundantBool {
+    /// Creates `n` copies of `false`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "a RedundantBool needs at least one copy");
+        RedundantBool((0..n).map(|_| WideCoin::new(false)).collect())
+    }
+
+    /// Returns the number of copies.
+    #[inline]
+    pub fn copies(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Stores the canonical bit pattern for `b` in every copy.
+    pub fn set(&self, b: bool) {
+        for copy in &self.0 {
+            copy.set(b);
+        }
+    }
+
+    /// Decodes each copy, then returns the quorum value.
+    pub fn get(&self) -> bool {
+        let votes = self.0.iter().filter(|copy| copy.to_bool()).count();
+        votes >= self.0.len() - votes
+    }
+
+    /// Returns the number of copies that decode to the quorum value.
+    pub fn healthy_copies(&self) -> usize {
+        let quorum = self.get();
+        self.0.iter().filter(|copy| copy.to_bool() == quorum).count()
+    }
+
+    /// Rewrites the canonical bit pattern of the quorum value to every copy.
+    pub fn scrub(&self) {
+        self.set(self.get());
+    }
+}
+
+impl_bool_like!(DoubleCoin, TripleCoin, ShadowCoin, RsCoin);
+
+#[cfg(test)]
+mod tests {
+    use super::{DoubleCoin, RsCoin, ShadowCoin, TripleCoin};
+    use crate::CoinError;
+
+    type Coin = crate::WideCoin<u8>;
+
+    /// Flips, in place, every bit of `coin` that is set in `mask`.
+    fn flip_mask(coin: &Coin, mask: u8) {
+        for bit in (0..8).filter(|&bit| mask & (1 << bit) != 0) {
+            coin.flip_bit(bit);
+        }
+    }
+
+    #[test]
+    fn triple_coin_survives_a_lost_copy() {
+        for b in [true, false] {
+            let coin = TripleCoin::from(b);
+            flip_mask(&coin.0[1].0, u8::MAX);
+            assert_eq!(coin.to_bool(), b);
+        }
+
+        let coin = TripleCoin::from(true);
+        flip_mask(&coin.0[0].0, u8::MAX);
+        assert!(coin.to_bool());
+    }
+
+    #[test]
+    fn triple_coin_copies_disagree() {
+        let coin = TripleCoin::from(false);
+        flip_mask(&coin.0[0].0, 0b1111_0111);
+        flip_mask(&coin.0[2].0, 0b0001_1110);
+        assert!(coin.to_bool());
+
+        coin.scrub();
+        for copy in &coin.0 {
+            assert_eq!(copy.0.as_bits(), u8::MAX);
+        }
+    }
+
+    #[test]
+    fn double_coin_prefers_the_more_confident_byte() {
+        let coin = DoubleCoin::new(true);
+        assert!(coin.to_bool());
+        assert!(!coin.disagreed());
+
+        // The second byte has decayed to `false`, but only just.
+        coin.second.set(0b0000_0111);
+        assert!(coin.to_bool());
+        assert!(coin.disagreed());
+
+        // Now the first byte is the weaker one.
+        coin.first.set(0b1000_0111);
+        coin.second.set(0b0000_0001);
+        assert!(!coin.to_bool());
+        assert!(coin.disagreed());
+
+        coin.first.set(0b1101_0111);
+        coin.second.set(0b0011_0000);
+        coin.scrub();
+        assert!(coin.disagreed());
+        assert_eq!((coin.first.get(), coin.second.get()), (u8::MAX, u8::MAX));
+
+        assert!(coin.to_bool());
+        assert!(!coin.disagreed());
+    }
+
+    #[test]
+    fn double_coin_prefers_first_when_equally_confident() {
+        let coin = DoubleCoin::new(false);
+        coin.first.set(0b0001_0001);
+        coin.second.set(0b1111_0110);
+        assert!(!coin.to_bool());
+        assert!(coin.disagreed());
+    }
+
+    #[test]
+    fn shadow_coin_restores_corrupted_primary() {
+        let coin = ShadowCoin::new(true);
+        coin.primary.set(0b0100_0101);
+        assert!(coin.to_bool());
+
+        coin.scrub();
+        assert_eq!((coin.primary.get(), coin.shadow.get()), (u8::MAX, u8::MAX));
+
+        let coin = ShadowCoin::new(false);
+        coin.primary.set(0b1110_1101);
+        coin.shadow.set(0b0000_1000);
+        assert!(!bool::from(coin.clone()));
+
+        coin.scrub();
+        assert_eq!((coin.primary.get(), coin.shadow.get()), (u8::MIN, u8::MIN));
+    }
+
+    #[test]
+    fn shadow_coin_prefers_primary_when_equally_confident() {
+        let coin = ShadowCoin::new(true);
+        coin.primary.set(0b1111_0111);
+        coin.shadow.set(0b0000_1000);
+        assert!(coin.to_bool());
+
+        coin.primary.set(0b0000_1000);
+        coin.shadow.set(0b1111_0111);
+        assert!(!coin.to_bool());
+    }
+
+    #[test]
+    fn triple_coin_copies_are_separate_cache_lines() {
+        assert_eq!(core::mem::size_of::<TripleCoin>(), 3 * 64);
+    }
+
+    #[test]
+    fn rs_coin_recovers_a_lost_byte_and_a_damaged_byte() {
+        for b in [true, false] {
+            let coin = RsCoin::from(b);
+            coin.0[1].set(0);
+            coin.0[3].set(coin.0[3].get() ^ 0b0110_1001);
+            assert_eq!(coin.try_to_bool(), Ok(b));
+
+            coin.scrub();
+            assert!(coin.0.iter().all(|bits| bits.get() == RsCoin::from(b).0[0].get()));
+        }
+    }
+
+    #[test]
+    fn rs_coin_fails_past_capacity() {
+        let coin = RsCoin::from(true);
+        coin.0[0].set(0);
+        coin.0[2].set(0b1110_1111);
+        coin.0[4].set(0b0111_1111);
+        assert_eq!(coin.try_to_bool(), Err(CoinError::Uncorrectable));
+        // Most of the 40 bits are still set.
+        assert!(coin.to_bool());
+
+        let coin = RsCoin::from(true);
+        for bits in &coin.0[..3] {
+            bits.set(0);
+        }
+        // Three lost bytes look like two damaged `false` bytes.
+        assert_eq!(coin.try_to_bool(), Ok(false));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn redundant_bool_survives_a_minority_of_lost_copies() {
+        use super::RedundantBool;
+
+        let flag = RedundantBool::new(5);
+        flag.set(true);
+        // Leaves 0b0010_0100 in copy 1, and wipes copy 4.
+        flip_mask(&flag.0[1], !0b0010_0100);
+        flip_mask(&flag.0[4], u8::MAX);
+        assert!(flag.get());
+        assert_eq!(flag.healthy_copies(), 3);
+
+        flag.scrub();
+        assert_eq!(flag.healthy_copies(), 5);
+        assert!(flag.0.iter().all(|copy| copy.as_bits() == u8::MAX));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn redundant_bool_loses_quorum() {
+        use super::RedundantBool;
+
+        let flag = RedundantBool::new(5);
+        flag.set(true);
+        for copy in &flag.0[..3] {
+            flip_mask(copy, !0b1000_0001);
+        }
+        // The corrupted copies now form the quorum.
+        assert!(!flag.get());
+        assert_eq!(flag.healthy_copies(), 3);
+    }
+}
@@ -0,0 +1,2896 @@
+//! The single-word [`WideCoin`] and its [`Coin`] alias.
+
+use core::borrow::Borrow;
+use core::cell::Cell;
+use core::fmt;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use core::str::FromStr;
+use core::{convert::From, hash::Hash};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::error::{CoinError, CoinParseError};
+use crate::word::{canonical, confidence, decode, Word};
+
+/// A bit flip resistant Boolean type, backed by a single byte
+///
+/// Prefer `Coin` to `bool` in safety-critical environments with long-lived
+/// variables, such as global variables. `Coin` uses the same space as `bool`.
+///
+/// `Coin` is the `u8`-backed [`WideCoin`]. Use a wider backing word when
+/// more than 3 bit flips need to be tolerated, either by naming it, or by
+/// enabling one of the `width-u16`, `width-u32` or `width-u64` features to
+/// retarget this alias for the whole build. When several are enabled, the
+/// widest one wins.
+#[cfg(not(any(feature = "width-u16", feature = "width-u32", feature = "width-u64")))]
+pub type Coin = WideCoin<u8>;
+
+/// A bit flip resistant Boolean type, backed by a `u16`
+///
+/// The `width-u16` feature is enabled, so `Coin` tolerates 7 bit flips.
+#[cfg(all(feature = "width-u16", not(any(feature = "width-u32", feature = "width-u64"))))]
+pub type Coin = WideCoin<u16>;
+
+/// A bit flip resistant Boolean type, backed by a `u32`
+///
+/// The `width-u32` feature is enabled, so `Coin` tolerates 15 bit flips.
+#[cfg(all(feature = "width-u32", not(feature = "width-u64")))]
+pub type Coin = WideCoin<u32>;
+
+/// A bit flip resistant Boolean type, backed by a `u64`
+///
+/// The `width-u64` feature is enabled, so `Coin` tolerates 31 bit flips.
+#[cfg(feature = "width-u64")]
+pub type Coin = WideCoin<u64>;
+
+/// A bit flip resistant Boolean type with a configurable backing word
+///
+/// Most code should use the [`Coin`] alias. `WideCoin<u16>`, `WideCoin<u32>`
+/// and `WideCoin<u64>` spend more space to tolerate more bit flips.
+///
+/// `Coin` imposes a small runtime and moderate ergonomic costs when being
+/// used in places where you would normally use `bool`. Therefore, you'll
+/// generally convert it to a standard `bool` as a local variable.
+///
+/// ## Examples
+///
+/// To use `Coin` in an `if` expression, it must first be converted to `bool`.
+///
+/// ```
+/// # use coin::Coin;
+/// # fn main() {
+/// let c = Coin::from(true);
+///
+/// if c.to_bool() {
+///     println!("Clunky, but effective.");
+/// }
+/// # }
+/// ```
+///
+/// ## Warnings
+///
+/// Rust's `true` is converted to numeric types, such as `i32`, it becomes 1.
+/// This bit pattern (`0b0000_0001`) is considered to be `false` within the
+/// internal representation that's used by `Coin`.
+///
+/// ```
+/// # use coin::Coin;
+/// # fn main() {
+/// let c = Coin::from(true);
+/// let one = i32::from(c.to_bool());
+/// assert_eq!(Coin::from(one == 1), c);
+/// # }
+/// ```
+///
+/// ## Background and implementation notes
+///
+/// A standard `bool` is truth-biased, because `false` matches a single
+/// bit pattern (all zeros). A single bit flip invalidates the value.
+///
+/// `Coin` counts the number of bits to determine its truth value. When
+/// 4 or more bits are 1, the value is interpreted as `true`. `Coin` can
+/// tolerate 3 bit flips per byte before an incorrect value is returned.
+/// In general, a `WideCoin<W>` is `true` when at least half of its bits
+/// are 1.
+///
+/// | set bits | bytes | `to_bool` | `confidence` | `classify`        |
+/// |----------|-------|-----------|--------------|-------------------|
+/// | 0 to 2   | 37    | `false`   | 8 to 4       | `DefinitelyFalse` |
+/// | 3        | 56    | `false`   | 2            | `Marginal`        |
+/// | 4        | 70    | `true`    | 0            | `Marginal`        |
+/// | 5        | 56    | `true`    | 2            | `Marginal`        |
+/// | 6 to 8   | 37    | `true`    | 4 to 8       | `DefinitelyTrue`  |
+///
+/// Of the 256 possible bytes, 163 decode as `true` and 93 as `false`.
+///
+/// For a more thorough introduction, see the talk "Software Security in the Presence of
+/// Faults" by Peter Gutmann (PDF <https://www.cs.auckland.ac.nz/~pgut001/pubs/software_faults.pdf>)
+/// (talk recording <https://www.youtube.com/watch?v=z0C7ymx5Jtk>).
+///
+/// ## Layout
+///
+/// `WideCoin<W>` is `#[repr(transparent)]`, so it has the same layout as
+/// `Cell<W>` and therefore as `W`.
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct WideCoin<W: Word>(Cell<W>);
+
+impl<W: Word> WideCoin<W> {
+    /// The number of set bits at which a coin decodes as `true`, half the
+    /// width of `W`.
+    ///
+    /// [`WideCoin::to_bool`] returns `true` exactly when
+    /// `count_ones() >= MAJORITY_THRESHOLD`, so a tie decodes as `true`.
+    /// For a `Coin`, this is 4.
+    pub const MAJORITY_THRESHOLD: u32 = W::BITS / 2;
+
+    /// Returns how many bit flips any canonical coin is guaranteed to
+    /// survive, `BITS / 2 - 1`.
+    ///
+    /// This is the worst case: a canonical `false` decodes as `true` after
+    /// `BITS / 2` flips, a canonical `true` only after one more (see
+    /// [`WideCoin::distance_to_flip`]).
+    ///
+    /// ```
+    /// # use coin::WideCoin;
+    /// # type Coin = coin::WideCoin<u8>;
+    /// assert_eq!(Coin::max_tolerable_flips(), 3);
+    /// assert_eq!(WideCoin::<u64>::max_tolerable_flips(), 31);
+    /// ```
+    #[inline]
+    pub const fn max_tolerable_flips() -> u32 {
+        Self::MAJORITY_THRESHOLD - 1
+    }
+
+    /// Creates a canonical coin for `b`. Unlike `From<bool>`, this can be
+    /// used in `const` contexts.
+    ///
+    /// There are no `TRUE` and `FALSE` associated consts. A coin holds its
+    /// bits in a `Cell`, and a `const` with interior mutability is copied
+    /// afresh at every use, so scrubbing or flipping `Coin::TRUE` would
+    /// silently act on a temporary. Clippy warns about such consts for that
+    /// reason. Call `new` in a `const` block instead, as below.
+    ///
+    /// ```
+    /// # use coin::Coin;
+    /// let armed = const { Coin::new(true) };
+    /// assert!(armed.to_bool());
+    /// ```
+    #[inline]
+    pub const fn new(b: bool) -> Self {
+        match b {
+            true => WideCoin(Cell::new(W::ONES)),
+            false => WideCoin(Cell::new(W::ZEROS)),
+        }
+    }
+
+    /// Creates a coin that stores `bits` verbatim.
+    ///
+    /// This is useful for wrapping a value read from a hardware register.
+    #[inline]
+    pub const fn from_bits(bits: W) -> Self {
+        WideCoin(Cell::new(bits))
+    }
+
+    /// Creates a coin from `bits`, which must be a canonical pattern.
+    ///
+    /// Returns [`CoinError::Uncorrectable`] for anything else. Use this for
+    /// strict wire formats, and [`WideCoin::from_bits`] to tolerate bit flips.
+    ///
+    /// ```
+    /// # use coin::CoinError;
+    /// # type Coin = coin::WideCoin<u8>;
+    /// assert!(Coin::from_bits_checked(0xff).is_ok());
+    /// assert_eq!(Coin::from_bits_checked(0xfe).unwrap_err(), CoinError::Uncorrectable);
+    /// ```
+    #[inline]
+    pub fn from_bits_checked(bits: W) -> Result<Self, CoinError> {
+        let coin = Self::from_bits(bits);
+        match coin.is_canonical() {
+            true => Ok(coin),
+            false => Err(CoinError::Uncorrectable),
+        }
+    }
+
+    /// Creates a canonical coin from bit `idx` of a register value.
+    ///
+    /// A single register bit has no redundancy to preserve, so its value is
+    /// spread into a fresh canonical pattern rather than copied bit for bit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is not less than 32.
+    ///
+    /// ```
+    /// # use coin::Coin;
+    /// let status: u32 = 0b0100;
+    /// assert!(Coin::read_bit(status, 2).to_bool());
+    /// assert!(!Coin::read_bit(status, 3).to_bool());
+    /// ```
+    #[inline]
+    pub fn read_bit(reg: u32, idx: u8) -> Self {
+        assert!(idx < 32, "bit index {idx} out of range for a 32-bit register");
+        Self::new(reg & (1 << idx) != 0)
+    }
+
+    /// Writes the decoded value to bit `idx` of `reg`, leaving its other bits
+    /// untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is not less than 32.
+    #[inline]
+    pub fn write_bit(&self, reg: &mut u32, idx: u8) {
+        assert!(idx < 32, "bit index {idx} out of range for a 32-bit register");
+        match self.to_bool() {
+            true => *reg |= 1 << idx,
+            false => *reg &= !(1 << idx),
+        }
+    }
+
+    /// Returns the raw bits without decoding or scrubbing them.
+    #[inline]
+    pub fn as_bits(&self) -> W {
+        self.0.get()
+    }
+
+    /// Flips bit `index` of the raw bits, simulating a fault.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the width of `W`.
+    pub fn flip_bit(&self, index: u8) {
+        let index = u32::from(index);
+        assert!(index < W::BITS, "bit index {index} out of range for a {}-bit coin", W::BITS);
+        self.0.set(self.0.get().flip_bit(index));
+    }
+
+    /// Flips `n` distinct bits of the raw bits, chosen at random.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the width of `W`.
+    #[cfg(feature = "rand")]
+    pub fn flip_n_random(&self, n: u32, rng: &mut impl rand::RngCore) {
+        assert!(n <= W::BITS, "can't flip {n} distinct bits of a {}-bit coin", W::BITS);
+
+        // A partial Fisher-Yates shuffle picks `n` distinct positions.
+        let mut positions: [u8; 64] = core::array::from_fn(|i| i as u8);
+
+        let mut bits = self.0.get();
+        for i in 0..n as usize {
+            let j = i + (rng.next_u32() % (W::BITS - i as u32)) as usize;
+            positions.swap(i, j);
+            bits = bits.flip_bit(u32::from(positions[i]));
+        }
+
+        self.0.set(bits);
+    }
+
+    /// Iterates over the raw bits, least significant first, without decoding
+    /// or scrubbing them.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let coin = Coin::from_bits(0b0000_0110);
+    /// let bits: Vec<bool> = coin.iter_bits().collect();
+    /// assert_eq!(bits, [false, true, true, false, false, false, false, false]);
+    /// ```
+    pub fn iter_bits(&self) -> impl Iterator<Item = bool> {
+        let val = self.0.get();
+        (0..W::BITS).map(move |index| val.bit(index))
+    }
+
+    /// Creates a coin from random bits, so that both the decoded value and
+    /// the amount of corruption vary. Useful for fuzzing.
+    ///
+    /// This is the same as sampling from `rand::distributions::Standard`.
+    #[cfg(feature = "rand")]
+    pub fn random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self
+    where
+        rand::distributions::Standard: rand::distributions::Distribution<W>,
+    {
+        Self::from_bits(rng.gen())
+    }
+
+    /// Consumes the coin, returning its decoded value.
+    #[inline(always)]
+    pub fn into_bool(self) -> bool {
+        self.to_bool()
+    }
+
+    /// Consumes the coin, returning its raw bits.
+    ///
+    /// Unlike converting the coin into its backing integer with `From`,
+    /// which returns the canonical pattern, this returns the bits as they
+    /// are, corruption and all.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.0.get()
+    }
+
+    /// Returns a canonical copy of the coin.
+    ///
+    /// `Clone` copies the raw bits verbatim, so a clone inherits any bit
+    /// flips that the original has suffered. `clone_canonical` decodes the
+    /// coin instead, and the copy starts out fresh. The original is left
+    /// untouched.
+    #[inline]
+    pub fn clone_canonical(&self) -> Self {
+        Self::new(self.to_bool())
+    }
+
+    /// Returns a canonical copy of the coin, leaving its bits untouched.
+    ///
+    /// This is the same as [`clone_canonical`](Self::clone_canonical), and
+    /// reads better when comparing against a known-good value.
+    #[inline]
+    pub fn to_canonical(&self) -> Self {
+        self.clone_canonical()
+    }
+
+    #[inline]
+    fn truthy() -> Self {
+        Self::new(true)
+    }
+
+    #[inline]
+    fn falsey() -> Self {
+        Self::new(false)
+    }
+
+    #[inline(always)]
+    pub fn to_bool(&self) -> bool {
+        decode(self.0.get())
+    }
+
+    /// Decodes the coin using arithmetic only, without data-dependent branches.
+    ///
+    /// `to_bool` may compile to a branch and its timing may depend on the
+    /// bits it decodes. Prefer this method when the coin gates a decision
+    /// that must not leak through timing.
+    #[inline(always)]
+    pub fn to_bool_ct(&self) -> bool {
+        self.below_ct() == 0
+    }
+
+    /// Compares decoded values using arithmetic only, without data-dependent
+    /// branches.
+    ///
+    /// `==` is not constant-time, as it decodes each coin with
+    /// [`WideCoin::to_bool`].
+    #[inline(always)]
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        (self.below_ct() ^ other.below_ct()) == 0
+    }
+
+    /// Returns 1 when fewer than half of the bits are set, and 0 otherwise.
+    #[inline(always)]
+    fn below_ct(&self) -> u32 {
+        let ones = self.0.get().count_ones();
+        // The high bit of `ones - MAJORITY_THRESHOLD` is set exactly when it
+        // underflows.
+        ones.wrapping_sub(Self::MAJORITY_THRESHOLD) >> (u32::BITS - 1)
+    }
+
+    /// Decodes the coin after a volatile read of its bits.
+    ///
+    /// The optimizer may assume that memory only changes when code writes
+    /// to it, so it is free to cache a coin's bits in a register or fold
+    /// repeated calls to [`WideCoin::to_bool`] together. A volatile read
+    /// forces every call to go back to memory, which is what you want when
+    /// the bits may be changed by radiation rather than by code.
+    ///
+    /// Volatile reads can't be merged, reordered or eliminated, so this is
+    /// slower than `to_bool` in tight loops.
+    #[inline(always)]
+    pub fn to_bool_volatile(&self) -> bool {
+        // SAFETY: the pointer comes from a live `Cell`, so it is valid and
+        // aligned. `Cell` is not `Sync`, so nothing writes to it concurrently.
+        let val = unsafe { core::ptr::read_volatile(self.0.as_ptr()) };
+        decode(val)
+    }
+
+    /// Returns `if_true` when the coin decodes as `true`, otherwise `if_false`.
+    ///
+    /// ```
+    /// # use coin::Coin;
+    /// let armed = Coin::from(true);
+    /// assert_eq!(armed.select("fire", "hold"), "fire");
+    /// ```
+    #[inline]
+    pub fn select<T>(&self, if_true: T, if_false: T) -> T {
+        match self.to_bool() {
+            true => if_true,
+            false => if_false,
+        }
+    }
+
+    /// Like [`WideCoin::select`], but only evaluates the chosen arm.
+    #[inline]
+    pub fn select_with<T>(&self, if_true: impl FnOnce() -> T, if_false: impl FnOnce() -> T) -> T {
+        match self.to_bool() {
+            true => if_true(),
+            false => if_false(),
+        }
+    }
+
+    /// Returns a canonical `true` if every coin decodes as `true`. An empty
+    /// iterator returns `true`.
+    pub fn and_all<I: IntoIterator<Item = WideCoin<W>>>(iter: I) -> Self {
+        WideCoin::from(iter.into_iter().all(|coin| coin.to_bool()))
+    }
+
+    /// Returns a canonical `true` if any coin decodes as `true`. An empty
+    /// iterator returns `false`.
+    pub fn or_all<I: IntoIterator<Item = WideCoin<W>>>(iter: I) -> Self {
+        WideCoin::from(iter.into_iter().any(|coin| coin.to_bool()))
+    }
+
+    /// Returns a canonical coin for both coins decoding as `true`. The same
+    /// as `self & rhs`, but reads left to right in a chain.
+    ///
+    /// ```
+    /// # use coin::Coin;
+    /// let (armed, ready, overridden) = (Coin::from(true), Coin::from(false), Coin::from(true));
+    /// assert!(armed.and(ready).or(overridden).to_bool());
+    /// ```
+    #[inline]
+    pub fn and(self, rhs: Self) -> Self {
+        self & rhs
+    }
+
+    /// Returns a canonical coin for either coin decoding as `true`. The same
+    /// as `self | rhs`.
+    #[inline]
+    pub fn or(self, rhs: Self) -> Self {
+        self | rhs
+    }
+
+    /// Returns a canonical coin for exactly one coin decoding as `true`. The
+    /// same as `self ^ rhs`.
+    #[inline]
+    pub fn xor(self, rhs: Self) -> Self {
+        self ^ rhs
+    }
+
+    /// Like `&`, but also reports whether either operand was
+    /// [`Decode::Marginal`].
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let (both, degraded) = Coin::from(true).and_checked(&Coin::from_bits(0b0111_0001));
+    /// assert!(both.to_bool());
+    /// assert!(degraded);
+    /// ```
+    #[inline]
+    pub fn and_checked(&self, rhs: &Self) -> (Self, bool) {
+        // Classify first, since the operator scrubs both operands.
+        let marginal = self.is_marginal() || rhs.is_marginal();
+        (self & rhs, marginal)
+    }
+
+    /// Like `|`, but also reports whether either operand was
+    /// [`Decode::Marginal`].
+    #[inline]
+    pub fn or_checked(&self, rhs: &Self) -> (Self, bool) {
+        // Classify first, since the operator scrubs both operands.
+        let marginal = self.is_marginal() || rhs.is_marginal();
+        (self | rhs, marginal)
+    }
+
+    #[inline(always)]
+    fn is_marginal(&self) -> bool {
+        self.classify() == Decode::Marginal
+    }
+
+    /// Returns the canonical coin for the majority of `samples`.
+    ///
+    /// A tie, including an empty slice, resolves to `true`, the same rule
+    /// that [`WideCoin::to_bool`] applies to a tied coin.
+    ///
+    /// ```
+    /// # use coin::Coin;
+    /// let coin = Coin::vote(&[true, false, false]);
+    /// assert!(!coin.to_bool());
+    /// ```
+    pub fn vote(samples: &[bool]) -> Self {
+        let trues = samples.iter().filter(|&&b| b).count();
+        WideCoin::from(trues >= samples.len() - trues)
+    }
+
+    /// Returns the canonical coin for the weighted majority of `entries`.
+    ///
+    /// Each copy contributes its weight to the value it decodes as. As with
+    /// [`WideCoin::vote`], a tie resolves to `true`.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let shielded = (Coin::from(true), 5);
+    /// let exposed = (Coin::from_bits(0b0001_0000), 1);
+    /// let coin = Coin::weighted_vote(&[shielded, exposed.clone(), exposed]);
+    /// assert!(coin.to_bool());
+    /// ```
+    pub fn weighted_vote(entries: &[(Self, u32)]) -> Self {
+        let (trues, falses) = entries.iter().fold((0u64, 0u64), |(trues, falses), (coin, weight)| {
+            match coin.to_bool() {
+                true => (trues + u64::from(*weight), falses),
+                false => (trues, falses + u64::from(*weight)),
+            }
+        });
+
+        WideCoin::from(trues >= falses)
+    }
+
+    /// Stores the canonical bit pattern for `b`.
+    #[inline]
+    pub fn set(&self, b: bool) {
+        self.0.set(canonical(b));
+    }
+
+    /// Stores the canonical bit pattern for the opposite of the decoded value.
+    #[inline]
+    pub fn toggle(&self) {
+        self.set(!self.to_bool());
+    }
+
+    /// Flips the stored value in place, the counterpart to `!coin`. The same
+    /// as [`toggle`](Self::toggle).
+    ///
+    /// ```
+    /// # use coin::Coin;
+    /// let armed = Coin::from(true);
+    /// armed.negate();
+    /// assert!(!armed.to_bool());
+    /// ```
+    #[inline]
+    pub fn negate(&self) {
+        self.toggle();
+    }
+
+    /// Parses the spellings commonly found in environment variables and
+    /// configuration files, ignoring ASCII case.
+    ///
+    /// `true`, `1`, `on` and `yes` are `true`. `false`, `0`, `off` and `no`
+    /// are `false`. The [`FromStr`] implementation is stricter, and only
+    /// accepts `true`, `false`, `1` and `0`.
+    pub fn parse_flexible(s: &str) -> Result<Self, CoinError> {
+        const TRUE: [&str; 4] = ["true", "1", "on", "yes"];
+        const FALSE: [&str; 4] = ["false", "0", "off", "no"];
+
+        if TRUE.iter().any(|word| s.eq_ignore_ascii_case(word)) {
+            Ok(WideCoin::truthy())
+        } else if FALSE.iter().any(|word| s.eq_ignore_ascii_case(word)) {
+            Ok(WideCoin::falsey())
+        } else {
+            Err(CoinParseError::new(s).into())
+        }
+    }
+
+    /// Decodes the coin, unless exactly half of its bits are set.
+    ///
+    /// [`WideCoin::to_bool`] resolves that tie as `true`, but a tied coin
+    /// is equally likely to have started out as either value. Use this
+    /// method to detect that the coin has decayed to a coin flip, which is
+    /// reported as [`CoinError::Ambiguous`].
+    ///
+    /// ```
+    /// # use coin::CoinError;
+    /// # type Coin = coin::WideCoin<u8>;
+    /// assert_eq!(Coin::from(0b0000_1111u8).try_to_bool(), Err(CoinError::Ambiguous));
+    /// assert_eq!(Coin::from(0b0001_1111u8).try_to_bool(), Ok(true));
+    /// ```
+    pub fn try_to_bool(&self) -> Result<bool, CoinError> {
+        let val = self.0.get();
+        match val.count_ones().cmp(&val.count_zeros()) {
+            core::cmp::Ordering::Greater => Ok(true),
+            core::cmp::Ordering::Less => Ok(false),
+            core::cmp::Ordering::Equal => Err(CoinError::Ambiguous),
+        }
+    }
+
+    /// Decodes the coin, panicking if exactly half of its bits are set.
+    ///
+    /// Use this in tests and debug builds to surface a coin that has decayed
+    /// to a coin flip, where [`WideCoin::to_bool`] would quietly resolve the
+    /// tie as `true`. [`WideCoin::try_to_bool`] reports the same condition
+    /// as an error instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coin is tied, with its raw bits in the message.
+    ///
+    /// ```should_panic
+    /// # type Coin = coin::WideCoin<u8>;
+    /// Coin::from_bits(0b1100_0011).to_bool_strict();
+    /// ```
+    #[track_caller]
+    pub fn to_bool_strict(&self) -> bool {
+        match self.try_to_bool() {
+            Ok(b) => b,
+            Err(_) => {
+                let width = W::BITS as usize + 2;
+                panic!("ambiguous coin: bits {:#0width$b} are evenly split", self.0.get())
+            }
+        }
+    }
+
+    /// Returns `true` when the raw bits are a canonical pattern, all set or
+    /// all clear.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let coin = Coin::from_bits(0b1111_0111);
+    /// assert!(!coin.is_canonical());
+    ///
+    /// coin.scrub();
+    /// assert!(coin.is_canonical());
+    /// ```
+    #[inline]
+    pub fn is_canonical(&self) -> bool {
+        let val = self.0.get();
+        val == W::ONES || val == W::ZEROS
+    }
+
+    /// Returns how far the coin is from the decision boundary.
+    ///
+    /// This is the difference between the number of set and clear bits. A
+    /// canonical `Coin` has a confidence of 8 and a tied one has 0. Each bit
+    /// flip towards the boundary lowers confidence by 2, so a falling
+    /// confidence is an early warning of bit rot.
+    #[inline]
+    pub fn confidence(&self) -> u8 {
+        confidence(self.0.get())
+    }
+
+    /// Decodes the coin and counts the bits that differ from the canonical
+    /// pattern for that value.
+    ///
+    /// Unlike [`scrub_reporting`](Self::scrub_reporting), this leaves the
+    /// coin untouched.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// assert_eq!(Coin::from_bits(0b1011_1110).to_bool_checked(), (true, 2));
+    /// ```
+    #[inline]
+    pub fn to_bool_checked(&self) -> (bool, u32) {
+        let val = self.0.get();
+        let b = decode(val);
+        let flips = match b {
+            true => val.count_zeros(),
+            false => val.count_ones(),
+        };
+
+        (b, flips)
+    }
+
+    /// Returns the most likely original value, and the number of bit
+    /// corrections needed to reach its canonical pattern.
+    ///
+    /// The nearest canonical pattern is the majority one, so the cost is
+    /// `min(count_ones, count_zeros)` and never exceeds half the bits. A tied
+    /// coin decodes as `true` at that maximal cost, which signals that the
+    /// decode is a guess. This is the same result as
+    /// [`to_bool_checked`](Self::to_bool_checked).
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// assert_eq!(Coin::from_bits(0b0010_0001).decode_ml(), (false, 2));
+    /// assert_eq!(Coin::from_bits(0b0011_1100).decode_ml(), (true, 4));
+    /// ```
+    #[inline]
+    pub fn decode_ml(&self) -> (bool, u32) {
+        self.to_bool_checked()
+    }
+
+    /// Returns how many more bit flips, in the worst direction, would change
+    /// the decoded value.
+    ///
+    /// A tie decodes as `true`, so the budget is lopsided. A canonical `true`
+    /// `Coin`, `0xFF`, survives 4 flips, because 4 flips only reach the tie,
+    /// and the 5th changes its value: it reports 5, not 4. A canonical
+    /// `false` one, `0x00`, is changed by the 4th flip and reports 4.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// assert_eq!(Coin::from(true).distance_to_flip(), 5);
+    /// assert_eq!(Coin::from(false).distance_to_flip(), 4);
+    /// assert_eq!(Coin::from_bits(0b0000_0111).distance_to_flip(), 1);
+    /// ```
+    #[inline]
+    pub fn distance_to_flip(&self) -> u32 {
+        let val = self.0.get();
+        let (ones, zeros) = (val.count_ones(), val.count_zeros());
+
+        match decode(val) {
+            true => (ones - zeros) / 2 + 1,
+            false => (zeros - ones).div_ceil(2),
+        }
+    }
+
+    /// Reports which way the bits have flipped, relative to the canonical
+    /// pattern for `expected`.
+    ///
+    /// Returns `Some(true)` when bits have flipped to one, `Some(false)` when
+    /// they have flipped to zero, and `None` when the coin is intact. The
+    /// canonical patterns are uniform, so every flip in a single coin goes
+    /// the same way. Across many coins, random noise shows up in both
+    /// directions, whereas a fault such as SRAM decaying to zero only ever
+    /// reports `Some(false)`.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let coin = Coin::from_bits(0b1110_1011);
+    /// assert_eq!(coin.flip_direction(true), Some(false));
+    /// ```
+    #[inline]
+    pub fn flip_direction(&self, expected: bool) -> Option<bool> {
+        match self.0.get() == canonical(expected) {
+            true => None,
+            false => Some(!expected),
+        }
+    }
+
+    /// Returns the number of raw bits that differ between the two coins,
+    /// without decoding or scrubbing either.
+    ///
+    /// Two copies of a value can decode the same while one of them is
+    /// quietly decaying. A growing distance exposes that.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let a = Coin::from(true);
+    /// let b = Coin::from_bits(0b1110_1111);
+    /// assert_eq!(a, b);
+    /// assert_eq!(a.bit_distance(&b), 1);
+    /// ```
+    #[inline]
+    pub fn bit_distance(&self, other: &Self) -> u32 {
+        (self.0.get() ^ other.0.get()).count_ones()
+    }
+
+    /// Returns the number of raw bits that differ from the canonical pattern
+    /// for `expected`.
+    ///
+    /// Compared with a golden value, a decaying coin reports a growing drift
+    /// before it decodes incorrectly. Once the drift passes
+    /// [`max_tolerable_flips`](Self::max_tolerable_flips), the coin decodes
+    /// as the opposite value.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let coin = Coin::from_bits(0b1011_0111);
+    /// assert_eq!(coin.drift_from(true), 2);
+    /// assert_eq!(coin.drift_from(false), 6);
+    /// ```
+    #[inline]
+    pub fn drift_from(&self, expected: bool) -> u32 {
+        (self.0.get() ^ canonical(expected)).count_ones()
+    }
+
+    /// Classifies the coin by how close it is to the decision boundary.
+    ///
+    /// A coin is [`Decode::Marginal`] when a single bit flip could take it
+    /// to, or across, a tie: 3, 4 or 5 set bits for a `Coin`.
+    ///
+    /// ```
+    /// # use coin::Decode;
+    /// # type Coin = coin::WideCoin<u8>;
+    /// assert_eq!(Coin::from(true).classify(), Decode::DefinitelyTrue);
+    /// assert_eq!(Coin::from_bits(0b0001_1100).classify(), Decode::Marginal);
+    /// ```
+    #[inline]
+    pub fn classify(&self) -> Decode {
+        match (self.confidence() <= 2, self.to_bool()) {
+            (true, _) => Decode::Marginal,
+            (false, true) => Decode::DefinitelyTrue,
+            (false, false) => Decode::DefinitelyFalse,
+        }
+    }
+
+    /// Rewrites the canonical bit pattern for the current value.
+    ///
+    /// Bit flips accumulate over time. A coin only decodes incorrectly once
+    /// more than half of its bits have flipped, so long-lived coins should
+    /// be scrubbed on a schedule to wipe out errors before they reach that
+    /// threshold.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let coin = Coin::from(0b1101_1011u8);
+    /// coin.scrub();
+    /// assert!(coin.to_bool());
+    /// ```
+    #[inline]
+    pub fn scrub(&self) {
+        self.degauss();
+    }
+
+    /// Scrubs the coin, returning how many bits had to be corrected.
+    ///
+    /// This is the Hamming distance between the old bits and the canonical
+    /// pattern. A clean coin reports 0. Feed it into your metrics to keep
+    /// an eye on how often coins are actually being corrected.
+    #[inline]
+    pub fn scrub_reporting(&self) -> u32 {
+        self.degauss()
+    }
+
+    /// Scrubs the coin, calling `on_correction` with the number of corrected
+    /// bits if any had to be corrected.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let coin = Coin::from(0b1110_1111u8);
+    /// let mut corrections = 0;
+    /// coin.scrub_with(|flips| corrections += flips);
+    /// assert_eq!(corrections, 1);
+    /// ```
+    pub fn scrub_with(&self, mut on_correction: impl FnMut(u32)) {
+        let flips = self.degauss();
+        if flips > 0 {
+            on_correction(flips);
+        }
+    }
+
+    /// Scrubs the coin, unless it is [`Decode::Marginal`].
+    ///
+    /// [`WideCoin::scrub`] trusts the decoded value, even when a single bit
+    /// flip could have changed it. `reinforce` only rewrites the canonical
+    /// pattern when the decode is decisive, and leaves a marginal coin for
+    /// manual review. Returns whether the coin was scrubbed.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let coin = Coin::from_bits(0b0111_1000);
+    /// assert!(!coin.reinforce());
+    /// assert_eq!(coin.as_bits(), 0b0111_1000);
+    /// ```
+    pub fn reinforce(&self) -> bool {
+        match self.classify() {
+            Decode::Marginal => false,
+            Decode::DefinitelyTrue | Decode::DefinitelyFalse => {
+                self.degauss();
+                true
+            }
+        }
+    }
+
+    fn degauss(&self) -> u32 {
+        let (b, flips) = self.to_bool_checked();
+        self.0.set(canonical(b));
+        flips
+    }
+}
+
+/// A type that can be read as a Boolean value
+///
+/// `Deref<Target = bool>` can't be implemented for coins, as there is no
+/// stored `bool` to borrow. Generic code can accept `impl BoolLike` instead,
+/// which covers both `bool` and the coin types.
+///
+/// ```
+/// use coin::{BoolLike, Coin};
+///
+/// fn armed(flag: &impl BoolLike) -> &'static str {
+///     if flag.truth() { "armed" } else { "safe" }
+/// }
+///
+/// assert_eq!(armed(&true), "armed");
+/// assert_eq!(armed(&Coin::from(false)), "safe");
+/// ```
+pub trait BoolLike {
+    /// Returns the value as a `bool`.
+    fn truth(&self) -> bool;
+}
+
+impl BoolLike for bool {
+    #[inline(always)]
+    fn truth(&self) -> bool {
+        *self
+    }
+}
+
+impl<W: Word> BoolLike for WideCoin<W> {
+    #[inline(always)]
+    fn truth(&self) -> bool {
+        self.to_bool()
+    }
+}
+
+/// The result of [`WideCoin::classify`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Decode {
+    /// More than one bit flip away from the boundary, decoding as `true`.
+    DefinitelyTrue,
+    /// Within one bit flip of the boundary.
+    Marginal,
+    /// More than one bit flip away from the boundary, decoding as `false`.
+    DefinitelyFalse,
+}
+
+/// Hashes the decoded value exactly as the `bool` it decodes to would hash,
+/// without scrubbing the coin.
+impl<W: Word> Hash for WideCoin<W> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.to_bool().hash(state);
+    }
+}
+
+/// Borrows the decoded value, so that a map keyed by coins can be queried
+/// with a `bool`.
+///
+/// ```
+/// # use coin::Coin;
+/// use std::collections::HashMap;
+///
+/// let mut labels = HashMap::new();
+/// labels.insert(Coin::from(true), "armed");
+/// assert_eq!(labels.get(&true), Some(&"armed"));
+/// ```
+impl<W: Word> Borrow<bool> for WideCoin<W> {
+    #[inline]
+    fn borrow(&self) -> &bool {
+        match self.to_bool() {
+            true => &true,
+            false => &false,
+        }
+    }
+}
+
+impl<W: Word> Eq for WideCoin<W> {}
+
+/// Compares decoded values, without scrubbing either coin.
+///
+/// This is not constant-time. Use [`WideCoin::ct_eq`] when the comparison
+/// gates a security decision.
+impl<W: Word> PartialEq for WideCoin<W> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bool() == other.to_bool()
+    }
+}
+
+impl<W: Word> PartialEq<bool> for WideCoin<W> {
+    #[inline(always)]
+    fn eq(&self, other: &bool) -> bool {
+        self.to_bool() == *other
+    }
+}
+
+impl<W: Word> PartialEq<WideCoin<W>> for bool {
+    #[inline(always)]
+    fn eq(&self, other: &WideCoin<W>) -> bool {
+        other == self
+    }
+}
+
+/// Orders by decoded value, `false` before `true`, without scrubbing either
+/// coin.
+impl<W: Word> Ord for WideCoin<W> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.to_bool().cmp(&other.to_bool())
+    }
+}
+
+impl<W: Word> PartialOrd for WideCoin<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Word> PartialOrd<bool> for WideCoin<W> {
+    #[inline]
+    fn partial_cmp(&self, other: &bool) -> Option<core::cmp::Ordering> {
+        self.to_bool().partial_cmp(other)
+    }
+}
+
+impl<W: Word> PartialOrd<WideCoin<W>> for bool {
+    #[inline]
+    fn partial_cmp(&self, other: &WideCoin<W>) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&other.to_bool())
+    }
+}
+
+impl<W: Word> From<&WideCoin<W>> for bool {
+    #[inline(always)]
+    fn from(c: &WideCoin<W>) -> Self {
+        c.to_bool()
+    }
+}
+
+/// Decodes the coin. Like the conversions from `&WideCoin` and `WideCoin`,
+/// this never scrubs it.
+impl<W: Word> From<&mut WideCoin<W>> for bool {
+    #[inline(always)]
+    fn from(c: &mut WideCoin<W>) -> Self {
+        c.to_bool()
+    }
+}
+
+impl<W: Word> From<WideCoin<W>> for bool {
+    #[inline(always)]
+    fn from(c: WideCoin<W>) -> Self {
+        c.to_bool()
+    }
+}
+
+/// Decodes the coin, returning `None` when it is [`Decode::Marginal`].
+///
+/// ```
+/// # type Coin = coin::WideCoin<u8>;
+/// assert_eq!(Option::<bool>::from(&Coin::from(false)), Some(false));
+/// assert_eq!(Option::<bool>::from(&Coin::from_bits(0b0111_1000)), None);
+/// ```
+impl<W: Word> From<&WideCoin<W>> for Option<bool> {
+    #[inline]
+    fn from(c: &WideCoin<W>) -> Self {
+        match c.classify() {
+            Decode::DefinitelyTrue => Some(true),
+            Decode::Marginal => None,
+            Decode::DefinitelyFalse => Some(false),
+        }
+    }
+}
+
+impl<W: Word> From<WideCoin<W>> for Option<bool> {
+    #[inline]
+    fn from(c: WideCoin<W>) -> Self {
+        Option::from(&c)
+    }
+}
+
+impl<W: Word> From<bool> for WideCoin<W> {
+    #[inline(always)]
+    fn from(b: bool) -> Self {
+        match b {
+            true  => WideCoin::truthy(),
+            false => WideCoin::falsey(),
+        }
+    }
+}
+
+/// Collects coins with [`WideCoin::and_all`].
+impl<W: Word> FromIterator<WideCoin<W>> for WideCoin<W> {
+    fn from_iter<I: IntoIterator<Item = WideCoin<W>>>(iter: I) -> Self {
+        WideCoin::and_all(iter)
+    }
+}
+
+/// Shows the decoded value alongside the raw bits and the confidence, such
+/// as `Coin { value: true, bits: 0b11111011, confidence: 6 }`.
+impl<W: Word> fmt::Debug for WideCoin<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = W::BITS as usize + 2;
+        f.debug_struct("Coin")
+            .field("value", &self.to_bool())
+            .field("bits", &format_args!("{:#0width$b}", self.0.get()))
+            .field("confidence", &self.confidence())
+            .finish()
+    }
+}
+
+/// Writes `true` or `false`, like `bool`.
+impl<W: Word> fmt::Display for WideCoin<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_bool(), f)
+    }
+}
+
+macro_rules! impl_raw_fmt {
+    ($($fmt:ident),*) => {
+        $(
+            /// Formats the raw bits, without decoding or scrubbing them.
+            impl<W: Word> fmt::$fmt for WideCoin<W> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::$fmt::fmt(&self.0.get(), f)
+                }
+            }
+        )*
+    };
+}
+
+impl_raw_fmt!(Binary, LowerHex, UpperHex);
+
+/// Parses `true`, `false`, `1` or `0`, ignoring ASCII case.
+///
+/// ```
+/// # use coin::Coin;
+/// let coin: Coin = "TRUE".parse().unwrap();
+/// assert!(coin.to_bool());
+/// ```
+impl<W: Word> FromStr for WideCoin<W> {
+    type Err = CoinError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("true") || s == "1" {
+            Ok(WideCoin::truthy())
+        } else if s.eq_ignore_ascii_case("false") || s == "0" {
+            Ok(WideCoin::falsey())
+        } else {
+            Err(CoinParseError::new(s).into())
+        }
+    }
+}
+
+impl WideCoin<u8> {
+    /// Views a byte buffer as a slice of coins, without copying.
+    ///
+    /// Coins can be scrubbed through a shared reference, so this borrows
+    /// the buffer mutably. Casting a `&[u8]` instead would be unsound.
+    ///
+    /// ```
+    /// # type Coin = coin::WideCoin<u8>;
+    /// let mut buffer = [0xff, 0x00, 0b1101_1111];
+    /// let coins = Coin::from_byte_slice(&mut buffer);
+    /// assert!(coins[2].to_bool());
+    ///
+    /// coins[2].scrub();
+    /// assert_eq!(buffer[2], 0xff);
+    /// ```
+    pub fn from_byte_slice(bytes: &mut [u8]) -> &[WideCoin<u8>] {
+        let cells = Cell::from_mut(bytes).as_slice_of_cells();
+        // SAFETY: `WideCoin<u8>` is `repr(transparent)` over `Cell<u8>`.
+        unsafe { &*(cells as *const [Cell<u8>] as *const [WideCoin<u8>]) }
+    }
+
+    /// Copies the raw bits into a [`CoinSnapshot`].
+    #[inline(always)]
+    pub fn snapshot(&self) -> CoinSnapshot {
+        CoinSnapshot(self.0.get())
+    }
+}
+
+/// A `Copy` snapshot of a [`Coin`]'s raw bits
+///
+/// The `Cell` inside a `Coin` stops it from being `Copy`. A `CoinSnapshot`
+/// holds the same byte, bit flips included, and decodes it with the same
+/// rule, so it can be passed around by value in hot paths. Convert it back
+/// into a `Coin` when it needs to be scrubbed or modified.
+///
+/// ```
+/// # use coin::CoinSnapshot;
+/// # type Coin = coin::WideCoin<u8>;
+/// let coin = Coin::from(true);
+/// let snapshot: CoinSnapshot = coin.snapshot();
+/// let copy = snapshot;
+///
+/// assert!(snapshot.to_bool() && copy.to_bool());
+/// assert_eq!(Coin::from(copy), coin);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct CoinSnapshot(u8);
+
+impl CoinSnapshot {
+    /// Returns the raw bits.
+    #[inline]
+    pub const fn as_bits(self) -> u8 {
+        self.0
+    }
+
+    #[inline(always)]
+    pub fn to_bool(self) -> bool {
+        decode(self.0)
+    }
+
+    /// Views a byte buffer, such as a memory-mapped file, as snapshots.
+    ///
+    /// A [`Coin`] can't be `Pod`, because it can be written through a shared
+    /// reference. Use [`Coin::from_byte_slice`] when the buffer is mutable.
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> &[CoinSnapshot] {
+        bytemuck::cast_slice(bytes)
+    }
+
+    /// Views snapshots as their raw bytes.
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn as_bytes(snapshots: &[CoinSnapshot]) -> &[u8] {
+        bytemuck::cast_slice(snapshots)
+    }
+}
+
+/// Restores the raw bits verbatim, without scrubbing them.
+impl From<CoinSnapshot> for WideCoin<u8> {
+    #[inline(always)]
+    fn from(snapshot: CoinSnapshot) -> Self {
+        WideCoin::from_bits(snapshot.0)
+    }
+}
+
+impl From<CoinSnapshot> for bool {
+    #[inline(always)]
+    fn from(snapshot: CoinSnapshot) -> Self {
+        snapshot.to_bool()
+    }
+}
+
+/// Stores `bits` as-is, leaving the decoding to [`WideCoin::to_bool`].
+///
+/// This differs from `From<bool>`. Rust's `true` is 1 when converted to an
+/// integer, and a lone set bit decodes as `false`:
+///
+/// ```
+/// # type Coin = coin::WideCoin<u8>;
+/// assert!(!Coin::from(true as u8).to_bool());
+/// assert!(Coin::from(0b0000_1111u8).to_bool());
+/// ```
+impl From<u8> for WideCoin<u8> {
+    #[inline(always)]
+    fn from(bits: u8) -> Self {
+        WideCoin::from_bits(bits)
+    }
+}
+
+/// Decodes `bits` by counting all 16 of them, returning a canonical coin.
+impl From<u16> for WideCoin<u8> {
+    #[inline(always)]
+    fn from(bits: u16) -> Self {
+        WideCoin::from(decode(bits))
+    }
+}
+
+/// Decodes `bits` by counting all 32 of them, returning a canonical coin.
+impl From<u32> for WideCoin<u8> {
+    #[inline(always)]
+    fn from(bits: u32) -> Self {
+        WideCoin::from(decode(bits))
+    }
+}
+
+// Converting a coin into its backing integer returns the canonical pattern
+// for its decoded value. Use `WideCoin::into_inner` for the raw bits.
+macro_rules! impl_from_coin_for_word {
+    ($($t:ty),*) => {
+        $(
+            impl From<WideCoin<$t>> for $t {
+                #[inline(always)]
+                fn from(c: WideCoin<$t>) -> Self {
+                    <$t>::from(&c)
+                }
+            }
+
+            impl From<&WideCoin<$t>> for $t {
+                #[inline(always)]
+                fn from(c: &WideCoin<$t>) -> Self {
+                    canonical(c.to_bool())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_coin_for_word!(u8, u16, u32, u64);
+
+/// Returns a canonical `false`, matching `bool::default()`.
+impl<W: Word> Default for WideCoin<W> {
+    #[inline(always)]
+    fn default() -> Self {
+        WideCoin::falsey()
+    }
+}
+
+/// Negates the decoded value, not the raw bits, returning a fresh coin.
+impl<W: Word> Not for WideCoin<W> {
+    type Output = WideCoin<W>;
+
+    #[inline(always)]
+    fn not(self) -> Self::Output {
+        !&self
+    }
+}
+
+impl<W: Word> Not for &WideCoin<W> {
+    type Output = WideCoin<W>;
+
+    #[inline(always)]
+    fn not(self) -> Self::Output {
+        self.degauss();
+        WideCoin::from(!self.to_bool())
+    }
+}
+
+// Binary operators decode both operands and always return a canonical coin.
+macro_rules! impl_bit_op {
+    ($op:ident, $method:ident, $assign_op:ident, $assign_method:ident, $bool_op:tt) => {
+        impl<W: Word> $op for WideCoin<W> {
+            type Output = WideCoin<W>;
+
+            #[inline(always)]
+            fn $method(self, rhs: Self) -> Self::Output {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl<W: Word> $op for &WideCoin<W> {
+            type Output = WideCoin<W>;
+
+            #[inline(always)]
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.degauss();
+                rhs.degauss();
+                WideCoin::from(self.to_bool() $bool_op rhs.to_bool())
+            }
+        }
+
+        impl<W: Word> $assign_op for WideCoin<W> {
+            #[inline(always)]
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+/// Serializes the decoded value as a `bool`.
+#[cfg(feature = "serde")]
+impl<W: Word> serde::Serialize for WideCoin<W> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bool(self.to_bool())
+    }
+}
+
+/// Deserializes a `bool`, always producing a canonical bit pattern.
+#[cfg(feature = "serde")]
+impl<'de, W: Word> serde::Deserialize<'de> for WideCoin<W> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        bool::deserialize(deserializer).map(WideCoin::from)
+    }
+}
+
+/// Samples a coin with random bits, as [`WideCoin::random`] does.
+#[cfg(feature = "rand")]
+impl<W: Word> rand::distributions::Distribution<WideCoin<W>> for rand::distributions::Standard
+where
+    rand::distributions::Standard: rand::distributions::Distribution<W>,
+{
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> WideCoin<W> {
+        WideCoin::random(rng)
+    }
+}
+
+/// Wipes the coin's bits, leaving it as a canonical `false`.
+#[cfg(feature = "zeroize")]
+impl<W: Word + zeroize::Zeroize> zeroize::Zeroize for WideCoin<W> {
+    fn zeroize(&mut self) {
+        self.0.get_mut().zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<W: Word> Drop for WideCoin<W> {
+    fn drop(&mut self) {
+        // SAFETY: the pointer comes from a live `&mut`, so it is valid and
+        // aligned. A volatile write keeps the wipe from being optimized away.
+        unsafe { core::ptr::write_volatile(self.0.as_ptr(), W::ZEROS) };
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<W: Word> zeroize::ZeroizeOnDrop for WideCoin<W> {}
+
+#[cfg(feature = "defmt")]
+macro_rules! impl_defmt {
+    ($($t:ty),*) => {
+        $(
+            /// Writes the decoded value, the raw bits and the confidence.
+            impl defmt::Format for WideCoin<$t> {
+                fn format(&self, f: defmt::Formatter<'_>) {
+                    defmt::write!(
+                        f,
+                        "{=bool} (bits: {:#x}, confidence: {=u8})",
+                        self.to_bool(),
+                        self.0.get(),
+                        self.confidence(),
+                    );
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "defmt")]
+impl_defmt!(u8, u16, u32, u64);
+
+// SAFETY: every backing word is an integer, for which all zeros is valid.
+// It is also the canonical `false` pattern.
+#[cfg(feature = "bytemuck")]
+unsafe impl<W: Word> bytemuck::Zeroable for WideCoin<W> {}
+
+// SAFETY: `WideCoin<W>` is `repr(transparent)` over `Cell<W>`, and every
+// bit pattern of the cell is a valid coin.
+#[cfg(feature = "bytemuck")]
+unsafe impl<W: Word> bytemuck::TransparentWrapper<Cell<W>> for WideCoin<W> {}
+
+// SAFETY: `CoinSnapshot` is `repr(transparent)` over `u8`, which is `Pod`,
+// and every byte is a valid snapshot.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for CoinSnapshot {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for CoinSnapshot {}
+
+/// Scrubs every coin in `coins`, returning the total number of corrected bits.
+///
+/// This is the operation that a periodic maintenance task should call. With
+/// the `simd` feature, `u8`-backed coins are decoded eight at a time.
+pub fn scrub_all<W: Word>(coins: &[WideCoin<W>]) -> u64 {
+    W::scrub_slice(coins)
+}
+
+pub(crate) fn scrub_all_scalar<W: Word>(coins: &[WideCoin<W>]) -> u64 {
+    coins.iter().map(|coin| u64::from(coin.degauss())).sum()
+}
+
+/// Returns `true` if every coin decodes as `true`, stopping at the first
+/// that doesn't. An empty slice returns `true`.
+#[inline]
+pub fn all<W: Word>(coins: &[WideCoin<W>]) -> bool {
+    coins.iter().all(WideCoin::to_bool)
+}
+
+/// Returns `true` if any coin decodes as `true`, stopping at the first that
+/// does. An empty slice returns `false`.
+#[inline]
+pub fn any<W: Word>(coins: &[WideCoin<W>]) -> bool {
+    coins.iter().any(WideCoin::to_bool)
+}
+
+/// Converts each of `bools` into a canonical [`Coin`].
+///
+/// This stands in for `From<Vec<bool>> for Vec<Coin>`, which the orphan
+/// rules forbid.
+///
+/// ```
+/// let coins = coin::from_bools(&[true, false]);
+/// assert_eq!(coin::to_bools(&coins), [true, false]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn from_bools(bools: &[bool]) -> Vec<Coin> {
+    bools.iter().copied().map(Coin::new).collect()
+}
+
+/// Decodes each coin in `coins`.
+#[cfg(feature = "alloc")]
+pub fn to_bools<W: Word>(coins: &[WideCoin<W>]) -> Vec<bool> {
+    coins.iter().map(WideCoin::to_bool).collect()
+}
+
+/// Converts each bit of `bits` into a canonical [`Coin`].
+#[cfg(feature = "bitvec")]
+pub fn from_bitslice<T, O>(bits: &bitvec::slice::BitSlice<T, O>) -> Vec<Coin>
+where
+    T: bitvec::store::BitStore,
+    O: bitvec::order::BitOrder,
+{
+    bits.iter().map(|bit| Coin::new(*bit)).collect()
+}
+
+/// Decodes each coin in `coins` into one bit of a `BitVec`.
+#[cfg(feature = "bitvec")]
+pub fn to_bitvec<W: Word>(coins: &[WideCoin<W>]) -> bitvec::vec::BitVec {
+    coins.iter().map(WideCoin::to_bool).collect()
+}
+
+impl_bool_like!(CoinSnapshot);
+
+#[cfg(test)]
+mod tests {
+    use super::WideCoin;
+    use crate::{CoinError, TripleCoin};
+    use std::format;
+    use std::vec::Vec;
+
+    // Pinned, so that the byte-level expectations below hold whichever
+    // `width-*` feature is enabled.
+    type Coin = WideCoin<u8>;
+
+    #[test]
+    fn one_bit_flip() {
+        let coin = Coin::from_bits(0b1111_1011);
+        assert!(coin.to_bool());
+    }
+
+    #[test]
+    fn two_bits_flipped() {
+        let coin = Coin::from_bits(0b1101_0011);
+        assert!(coin.to_bool());
+    }
+
+    #[test]
+    fn three_bits_flipped() {
+        let coin = Coin::from_bits(0b1101_0011);
+        assert!(coin.to_bool());
+    }
+
+    #[test]
+    fn four_bits_flipped() {
+        let coin = Coin::from_bits(0b1100_0011);
+        assert!(coin.to_bool());
+    }
+
+    #[test]
+    fn five_bits_flipped() {
+        let coin = Coin::from_bits(0b1000_0011);
+        assert!(!coin.to_bool());
+    }
+
+    #[test]
+    fn const_constructors() {
+        let armed = const { Coin::new(true) };
+        let safe = const { WideCoin::<u64>::new(false) };
+
+        assert!(armed.to_bool());
+        assert_eq!(armed.as_bits(), u8::MAX);
+        assert!(!safe.to_bool());
+        assert_eq!(safe.as_bits(), u64::MIN);
+    }
+
+    #[test]
+    fn from_u8_stores_raw_bits() {
+        assert!(Coin::from(0b0000_1111u8).to_bool());
+        assert!(!Coin::from(0b0000_0011u8).to_bool());
+        assert_eq!(Coin::from(0b0000_0011u8).as_bits(), 0b0000_0011);
+    }
+
+    #[test]
+    fn from_wider_integers_decodes_all_bits() {
+        assert_eq!(Coin::from(0x00ffu16).as_bits(), u8::MAX);
+        assert_eq!(Coin::from(0x007fu16).as_bits(), u8::MIN);
+        assert_eq!(Coin::from(0x0f0f_0f0fu32).as_bits(), u8::MAX);
+        assert_eq!(Coin::from(0x0000_ff00u32).as_bits(), u8::MIN);
+    }
+
+    #[test]
+    fn eq_bool() {
+        let yes = Coin::from(true);
+        let no = Coin::from(false);
+
+        assert!(yes == true);
+        assert!(no == false);
+        assert!(yes != false);
+        assert!(true == yes);
+        assert!(false == no);
+        assert!(false != yes);
+    }
+
+    #[test]
+    fn eq_bool_compares_decoded_value() {
+        let coin = Coin::from_bits(0b0011_1001);
+        assert!(coin == true);
+        assert!(false != coin);
+    }
+
+    #[test]
+    fn raw_bits_formatting() {
+        let coin = Coin::from_bits(0b0000_1011);
+        assert_eq!(format!("{coin:08b}"), "00001011");
+        assert_eq!(format!("{coin:#x}"), "0xb");
+        assert_eq!(format!("{coin:02X}"), "0B");
+        assert_eq!(coin.as_bits(), 0b0000_1011);
+
+        let coin = Coin::from_bits(0b1110_1111);
+        assert_eq!(format!("{coin:b}"), "11101111");
+        assert_eq!(format!("{coin:x}"), "ef");
+        assert_eq!(format!("{coin:X}"), "EF");
+
+        assert_eq!(format!("{:#06x}", WideCoin::<u16>::from_bits(0x00fe)), "0x00fe");
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Coin::from(true)), "true");
+        assert_eq!(format!("{}", Coin::from(false)), "false");
+        assert_eq!(format!("{:>6}", WideCoin::<u32>::from(true)), "  true");
+
+        let coin = Coin::from_bits(0b0100_0010);
+        assert_eq!(format!("{coin}"), "false");
+    }
+
+    #[test]
+    fn from_str() {
+        for s in ["true", "True", "TRUE", "1"] {
+            assert_eq!(s.parse::<Coin>().unwrap().as_bits(), u8::MAX);
+        }
+
+        for s in ["false", "False", "FALSE", "0"] {
+            assert_eq!(s.parse::<Coin>().unwrap().as_bits(), u8::MIN);
+        }
+    }
+
+    #[test]
+    fn from_str_rejects_other_words() {
+        assert!("yes".parse::<Coin>().is_err());
+        assert!("".parse::<Coin>().is_err());
+        assert!(" true".parse::<Coin>().is_err());
+    }
+
+    #[test]
+    fn scrub() {
+        let coin = Coin::from_bits(0b1011_1110);
+        coin.scrub();
+        assert_eq!(coin.as_bits(), u8::MAX);
+
+        let coin = Coin::from_bits(0b0000_1001);
+        coin.scrub();
+        assert_eq!(coin.as_bits(), u8::MIN);
+    }
+
+    #[test]
+    fn try_to_bool() {
+        assert_eq!(Coin::from(0b0000_1111u8).try_to_bool(), Err(CoinError::Ambiguous));
+        assert_eq!(Coin::from(0b1010_0101u8).try_to_bool(), Err(CoinError::Ambiguous));
+        assert_eq!(Coin::from(0b1110_1111u8).try_to_bool(), Ok(true));
+        assert_eq!(Coin::from(0b0011_0111u8).try_to_bool(), Ok(true));
+        assert_eq!(Coin::from(0b0000_0111u8).try_to_bool(), Ok(false));
+        assert_eq!(Coin::from(false).try_to_bool(), Ok(false));
+        assert_eq!(WideCoin::<u16>::from(true).try_to_bool(), Ok(true));
+    }
+
+    #[test]
+    fn register_bits() {
+        let mut reg: u32 = 0x8000_0005;
+        for idx in 0..32 {
+            let coin = Coin::read_bit(reg, idx);
+            assert!(coin.is_canonical());
+            assert_eq!(coin.to_bool(), matches!(idx, 0 | 2 | 31), "bit {idx}");
+        }
+
+        Coin::from(false).write_bit(&mut reg, 0);
+        Coin::from_bits(0b1101_1110).write_bit(&mut reg, 4);
+        WideCoin::<u64>::from(true).write_bit(&mut reg, 31);
+        assert_eq!(reg, 0x8000_0014);
+    }
+
+    #[test]
+    #[should_panic]
+    fn register_bit_out_of_range() {
+        Coin::read_bit(0, 32);
+    }
+
+    #[test]
+    fn to_bool_strict_decodes_decisive_coins() {
+        assert!(Coin::from(true).to_bool_strict());
+        assert!(!Coin::from(false).to_bool_strict());
+        assert!(Coin::from_bits(0b1110_1011).to_bool_strict());
+        assert!(!Coin::from_bits(0b0001_0100).to_bool_strict());
+        assert!(Coin::from_bits(0b0111_0011).to_bool_strict());
+        assert!(!Coin::from_bits(0b0010_1100).to_bool_strict());
+        assert!(!WideCoin::<u32>::from_bits(0x0010_00ff).to_bool_strict());
+    }
+
+    #[test]
+    #[should_panic(expected = "ambiguous coin: bits 0b10100101")]
+    fn to_bool_strict_panics_on_a_tie() {
+        Coin::from_bits(0b1010_0101).to_bool_strict();
+    }
+
+    #[test]
+    fn confidence() {
+        assert_eq!(Coin::from(true).confidence(), 8);
+        assert_eq!(Coin::from(false).confidence(), 8);
+        assert_eq!(Coin::from(0b1111_1011u8).confidence(), 6);
+        assert_eq!(Coin::from(0b0010_0100u8).confidence(), 4);
+        assert_eq!(Coin::from(0b0000_0111u8).confidence(), 2);
+        assert_eq!(Coin::from(0b1100_0011u8).confidence(), 0);
+        assert_eq!(WideCoin::<u64>::from(true).confidence(), 64);
+    }
+
+    #[test]
+    fn majority_threshold_is_the_decode_boundary() {
+        fn check<W: super::Word>(at_threshold: W, below_threshold: W) {
+            assert_eq!(at_threshold.count_ones(), WideCoin::<W>::MAJORITY_THRESHOLD);
+            assert_eq!(below_threshold.count_ones(), WideCoin::<W>::MAJORITY_THRESHOLD - 1);
+            assert!(WideCoin::from_bits(at_threshold).to_bool());
+            assert!(!WideCoin::from_bits(below_threshold).to_bool());
+        }
+
+        assert_eq!(Coin::MAJORITY_THRESHOLD, 4);
+        check::<u8>(0b1010_1010, 0b1010_1000);
+        check::<u16>(0x0ff0, 0x07f0);
+        check::<u32>(0x00ff_ff00, 0x007f_ff00);
+        check::<u64>(0x0000_ffff_ffff_0000, 0x0000_7fff_ffff_0000);
+
+        for bits in 0..=u8::MAX {
+            let expected = bits.count_ones() >= Coin::MAJORITY_THRESHOLD;
+            assert_eq!(Coin::from_bits(bits).to_bool(), expected, "{bits:#010b}");
+        }
+    }
+
+    #[test]
+    fn max_tolerable_flips() {
+        assert_eq!(WideCoin::<u8>::max_tolerable_flips(), 3);
+        assert_eq!(WideCoin::<u16>::max_tolerable_flips(), 7);
+        assert_eq!(WideCoin::<u32>::max_tolerable_flips(), 15);
+        assert_eq!(WideCoin::<u64>::max_tolerable_flips(), 31);
+
+        for b in [true, false] {
+            let coin = Coin::from(b);
+            assert!(coin.distance_to_flip() > Coin::max_tolerable_flips());
+        }
+        assert_eq!(Coin::from(false).distance_to_flip(), Coin::max_tolerable_flips() + 1);
+    }
+
+    #[test]
+    fn coin_width_follows_features() {
+        #[cfg(not(any(feature = "width-u16", feature = "width-u32", feature = "width-u64")))]
+        assert_eq!(crate::Coin::max_tolerable_flips(), 3);
+        #[cfg(all(feature = "width-u16", not(any(feature = "width-u32", feature = "width-u64"))))]
+        assert_eq!(crate::Coin::max_tolerable_flips(), 7);
+        #[cfg(all(feature = "width-u32", not(feature = "width-u64")))]
+        assert_eq!(crate::Coin::max_tolerable_flips(), 15);
+        #[cfg(feature = "width-u64")]
+        assert_eq!(crate::Coin::max_tolerable_flips(), 31);
+    }
+
+    #[test]
+    fn bool_like_accepts_bools_and_coins() {
+        use super::BoolLike;
+
+        fn count_true<T: BoolLike>(values: &[T]) -> usize {
+            values.iter().filter(|value| value.truth()).count()
+        }
+
+        assert_eq!(count_true(&[true, false, true]), 2);
+        assert_eq!(count_true(&[Coin::from(true), Coin::from_bits(0b0110_0000), Coin::from_bits(0b1101_1101)]), 2);
+        assert_eq!(count_true(&[WideCoin::<u32>::from(false)]), 0);
+        assert_eq!(count_true(&[TripleCoin::new(true), TripleCoin::new(false)]), 1);
+        assert!(Coin::from(true).snapshot().truth());
+    }
+
+    /// Tallies the decode of every byte, grouped by the number of set bits.
+    ///
+    /// | set bits | bytes | `to_bool` | `confidence` | `classify`        |
+    /// |----------|-------|-----------|--------------|-------------------|
+    /// | 0        | 1     | `false`   | 8            | `DefinitelyFalse` |
+    /// | 1        | 8     | `false`   | 6            | `DefinitelyFalse` |
+    /// | 2        | 28    | `false`   | 4            | `DefinitelyFalse` |
+    /// | 3        | 56    | `false`   | 2            | `Marginal`        |
+    /// | 4        | 70    | `true`    | 0            | `Marginal`        |
+    /// | 5        | 56    | `true`    | 2            | `Marginal`        |
+    /// | 6        | 28    | `true`    | 4            | `DefinitelyTrue`  |
+    /// | 7        | 8     | `true`    | 6            | `DefinitelyTrue`  |
+    /// | 8        | 1     | `true`    | 8            | `DefinitelyTrue`  |
+    #[test]
+    fn decode_table_for_every_byte() {
+        use super::Decode;
+
+        // Every byte with the same number of set bits decodes the same way.
+        let mut rows: [Option<(bool, u8, Decode)>; 9] = [None; 9];
+        let mut counts = [0u32; 9];
+        for bits in 0..=u8::MAX {
+            let coin = Coin::from_bits(bits);
+            let ones = bits.count_ones() as usize;
+            let row = (coin.to_bool(), coin.confidence(), coin.classify());
+            assert_eq!(*rows[ones].get_or_insert(row), row, "{bits:#010b}");
+            counts[ones] += 1;
+        }
+
+        let rows = rows.map(Option::unwrap);
+        assert_eq!(
+            rows,
+            [
+                (false, 8, Decode::DefinitelyFalse),
+                (false, 6, Decode::DefinitelyFalse),
+                (false, 4, Decode::DefinitelyFalse),
+                (false, 2, Decode::Marginal),
+                (true, 0, Decode::Marginal),
+                (true, 2, Decode::Marginal),
+                (true, 4, Decode::DefinitelyTrue),
+                (true, 6, Decode::DefinitelyTrue),
+                (true, 8, Decode::DefinitelyTrue),
+            ]
+        );
+
+        // The number of bytes with k set bits is the binomial coefficient C(8, k).
+        for (k, count) in counts.iter().enumerate() {
+            let binomial = (0..k as u32).fold(1, |c, i| c * (8 - i) / (i + 1));
+            assert_eq!(*count, binomial, "{k} set bits");
+        }
+
+        let tally = |f: fn(&(bool, u8, Decode)) -> bool| -> u32 {
+            rows.iter().zip(counts).filter(|(row, _)| f(row)).map(|(_, n)| n).sum()
+        };
+        assert_eq!(tally(|row| row.0), 163);
+        assert_eq!(tally(|row| !row.0), 93);
+        assert_eq!(tally(|row| row.2 == Decode::DefinitelyTrue), 37);
+        assert_eq!(tally(|row| row.2 == Decode::Marginal), 182);
+        assert_eq!(tally(|row| row.2 == Decode::DefinitelyFalse), 37);
+    }
+
+    #[test]
+    fn to_bool_ct_matches_to_bool() {
+        for bits in 0..=u8::MAX {
+            let coin = Coin::from_bits(bits);
+            assert_eq!(coin.to_bool_ct(), coin.to_bool(), "{bits:#010b}");
+        }
+
+        for bits in [0, 0x00ff_ffff, 0x0000_ffff, 0x0000_7fff, u32::MAX] {
+            let coin = WideCoin::<u32>::from_bits(bits);
+            assert_eq!(coin.to_bool_ct(), coin.to_bool(), "{bits:#x}");
+        }
+    }
+
+    #[test]
+    fn ct_eq_matches_decoded_comparison() {
+        for a in 0..=u8::MAX {
+            let x = Coin::from_bits(a);
+            for b in 0..=u8::MAX {
+                let y = Coin::from_bits(b);
+                assert_eq!(x.ct_eq(&y), x.to_bool() == y.to_bool(), "{a:#010b} {b:#010b}");
+            }
+        }
+
+        let (x, y) = (WideCoin::<u16>::from_bits(0x00ff), WideCoin::<u16>::from_bits(0x007f));
+        assert!(!x.ct_eq(&y));
+        assert!(x.ct_eq(&WideCoin::from(true)));
+    }
+
+    #[test]
+    fn to_bool_volatile_rereads_memory() {
+        let coin = Coin::from(true);
+        assert!(coin.to_bool_volatile());
+
+        // Clear every bit but bit 4, leaving 0b0001_0000 in the same cell.
+        for bit in (0..8).filter(|&bit| bit != 4) {
+            coin.flip_bit(bit);
+        }
+        assert!(!coin.to_bool_volatile());
+    }
+
+    #[test]
+    fn from_bits_as_bits_round_trip() {
+        for bits in 0..=u8::MAX {
+            let coin = Coin::from_bits(bits);
+            assert_eq!(coin.as_bits(), bits);
+            assert_eq!(coin.as_bits(), bits);
+        }
+
+        let coin = WideCoin::<u32>::from_bits(0xdead_beef);
+        assert_eq!(coin.as_bits(), 0xdead_beef);
+    }
+
+    #[test]
+    fn coin_macro() {
+        let armed = const { coin!(true) };
+        assert!(armed.to_bool() && armed.is_canonical());
+        assert!(!coin!(1 > 2).to_bool());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn coin_macro_list() {
+        let flags = coin![true, false, true,];
+        let decoded: Vec<bool> = flags.iter().map(|coin| coin.to_bool()).collect();
+        assert_eq!(decoded, [true, false, true]);
+    }
+
+    #[test]
+    fn from_byte_slice() {
+        let mut buffer = [u8::MAX, 0b0000_1000, 0b1110_1101, 0b0011_0000];
+        let coins = Coin::from_byte_slice(&mut buffer);
+        assert_eq!(coins.len(), 4);
+
+        let decoded = [true, false, true, false];
+        for (coin, b) in coins.iter().zip(decoded) {
+            assert_eq!(coin.to_bool(), b);
+            coin.scrub();
+        }
+
+        assert_eq!(buffer, [u8::MAX, u8::MIN, u8::MAX, u8::MIN]);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize() {
+        use zeroize::Zeroize;
+
+        let mut coin = Coin::from_bits(0b1011_1111);
+        coin.zeroize();
+        assert_eq!(coin.as_bits(), 0);
+
+        let mut coin = WideCoin::<u64>::from(true);
+        coin.zeroize();
+        assert_eq!(coin.as_bits(), 0);
+    }
+
+    #[test]
+    fn scrub_reporting() {
+        assert_eq!(Coin::from(true).scrub_reporting(), 0);
+        assert_eq!(Coin::from(false).scrub_reporting(), 0);
+
+        let coin = Coin::from_bits(0b1101_1110);
+        assert_eq!(coin.scrub_reporting(), 2);
+        assert_eq!(coin.as_bits(), u8::MAX);
+        assert_eq!(coin.scrub_reporting(), 0);
+
+        let coin = WideCoin::<u16>::from_bits(0x0101);
+        assert_eq!(coin.scrub_reporting(), 2);
+        assert_eq!(coin.as_bits(), u16::MIN);
+    }
+
+    #[test]
+    fn select() {
+        assert_eq!(Coin::from(true).select(1, 2), 1);
+        assert_eq!(Coin::from(false).select(1, 2), 2);
+        assert_eq!(Coin::from_bits(0b1011_0011).select('a', 'b'), 'a');
+    }
+
+    #[test]
+    fn select_with_only_evaluates_chosen_arm() {
+        let chosen = Coin::from(true).select_with(|| 1, || unreachable!());
+        assert_eq!(chosen, 1);
+
+        let chosen = Coin::from(false).select_with(|| unreachable!(), || 2);
+        assert_eq!(chosen, 2);
+    }
+
+    #[test]
+    fn toggle() {
+        let coin = Coin::from(true);
+        coin.toggle();
+        assert_eq!(coin.as_bits(), u8::MIN);
+        coin.toggle();
+        assert_eq!(coin.as_bits(), u8::MAX);
+
+        let coin = Coin::from_bits(0b0100_1000);
+        coin.toggle();
+        assert_eq!(coin.as_bits(), u8::MAX);
+    }
+
+    #[test]
+    fn negate() {
+        let coin = Coin::from(false);
+        coin.negate();
+        assert_eq!(coin.as_bits(), u8::MAX);
+        coin.negate();
+        assert_eq!(coin.as_bits(), u8::MIN);
+
+        // Decodes as `true`, despite bits 1 and 5 having flipped.
+        let coin = Coin::from_bits(0b1101_1101);
+        coin.negate();
+        assert_eq!(coin.as_bits(), u8::MIN);
+        assert_eq!(coin, !Coin::from_bits(0b1101_1101));
+    }
+
+    #[test]
+    fn set() {
+        let coin = Coin::from_bits(0b1110_0111);
+        coin.set(true);
+        assert_eq!(coin.as_bits(), u8::MAX);
+
+        let coin = Coin::from_bits(0b1110_0111);
+        coin.set(false);
+        assert_eq!(coin.as_bits(), u8::MIN);
+    }
+
+    #[test]
+    fn and_all_or_all() {
+        assert_eq!(Coin::and_all([]).as_bits(), u8::MAX);
+        assert_eq!(Coin::or_all([]).as_bits(), u8::MIN);
+
+        let all_true = || [Coin::from(true), Coin::from(true)];
+        assert!(Coin::and_all(all_true()).to_bool());
+        assert!(Coin::or_all(all_true()).to_bool());
+
+        let all_false = || [Coin::from(false), Coin::from(false)];
+        assert!(!Coin::and_all(all_false()).to_bool());
+        assert!(!Coin::or_all(all_false()).to_bool());
+
+        let mixed = || [Coin::from(true), Coin::from_bits(0b0001_0001), Coin::from(true)];
+        assert_eq!(Coin::and_all(mixed()).as_bits(), u8::MIN);
+        assert_eq!(Coin::or_all(mixed()).as_bits(), u8::MAX);
+
+        let corrupted = || [Coin::from(true), Coin::from_bits(0b1110_1110)];
+        assert_eq!(Coin::and_all(corrupted()).as_bits(), u8::MAX);
+    }
+
+    #[test]
+    fn collect_ands_coins() {
+        let coin: Coin = [Coin::from(true), Coin::from_bits(0b0111_1100)].into_iter().collect();
+        assert!(coin.to_bool());
+
+        let coin: Coin = [Coin::from(true), Coin::from(false)].into_iter().collect();
+        assert!(!coin.to_bool());
+    }
+
+    #[test]
+    fn into_canonical_bits() {
+        assert_eq!(u8::from(Coin::from(true)), u8::MAX);
+        assert_eq!(u8::from(Coin::from(false)), u8::MIN);
+
+        let coin = Coin::from_bits(0b1110_1011);
+        assert_eq!(u8::from(&coin), u8::MAX);
+        assert_eq!(coin.as_bits(), 0b1110_1011);
+        assert_eq!(u8::from(Coin::from_bits(0b0000_0110)), u8::MIN);
+        assert_eq!(u64::from(WideCoin::<u64>::from_bits(0x7fff_ffff)), u64::MIN);
+    }
+
+    #[test]
+    fn debug_shows_value_and_bits() {
+        let debug = format!("{:?}", Coin::from_bits(0b1111_1011));
+        assert_eq!(debug, "Coin { value: true, bits: 0b11111011, confidence: 6 }");
+
+        let debug = format!("{:?}", WideCoin::<u16>::from_bits(0x0003));
+        assert!(debug.contains("value: false"), "{debug}");
+        assert!(debug.contains("bits: 0b0000000000000011"), "{debug}");
+    }
+
+    #[test]
+    fn into_inner() {
+        assert_eq!(Coin::from(true).into_inner(), u8::MAX);
+        assert_eq!(Coin::from(false).into_inner(), u8::MIN);
+        assert_eq!(Coin::from_bits(0b1110_1011).into_inner(), 0b1110_1011);
+        assert_eq!(WideCoin::<u16>::from_bits(0x0ff0).into_inner(), 0x0ff0);
+    }
+
+    #[test]
+    fn ord_matches_bool() {
+        let corrupted = |b: bool| Coin::from_bits(if b { 0b1011_1101 } else { 0b0100_0010 });
+
+        for a in [false, true] {
+            for b in [false, true] {
+                assert_eq!(Coin::from(a).cmp(&Coin::from(b)), a.cmp(&b));
+                assert_eq!(corrupted(a).cmp(&corrupted(b)), a.cmp(&b));
+                assert_eq!(corrupted(a).partial_cmp(&b), a.partial_cmp(&b));
+                assert_eq!(a.partial_cmp(&corrupted(b)), a.partial_cmp(&b));
+            }
+        }
+    }
+
+    // Coins are fine as map keys despite their `Cell`: the hash only changes
+    // if enough bits flip to change the decoded value.
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn hashes_like_bool() {
+        use std::collections::hash_map::RandomState;
+        use std::collections::HashMap;
+        use std::hash::BuildHasher;
+
+        let state = RandomState::new();
+        for bits in [u8::MAX, 0b1011_0111, 0b0000_1111, 0b0010_0001, u8::MIN] {
+            let coin = Coin::from_bits(bits);
+            assert_eq!(state.hash_one(&coin), state.hash_one(coin.to_bool()), "{bits:#010b}");
+        }
+        assert_eq!(state.hash_one(WideCoin::<u64>::from(false)), state.hash_one(false));
+
+        let mut map = HashMap::new();
+        map.insert(Coin::from_bits(0b1101_1110), "on");
+        map.insert(Coin::from_bits(0b0100_0000), "off");
+        assert_eq!(map.get(&true), Some(&"on"));
+        assert_eq!(map.get(&false), Some(&"off"));
+    }
+
+    #[test]
+    fn sort_leaves_bits_intact() {
+        let bits = [0b1011_1101, 0b0000_0000, 0b0100_0010, 0b1111_1111, 0b0011_0000, 0b1101_0111];
+        let mut coins: Vec<Coin> = bits.iter().copied().map(Coin::from_bits).collect();
+        coins.sort();
+
+        let sorted: Vec<u8> = coins.iter().map(Coin::as_bits).collect();
+        assert_eq!(sorted, [0b0000_0000, 0b0100_0010, 0b0011_0000, 0b1011_1101, 0b1111_1111, 0b1101_0111]);
+    }
+
+    #[test]
+    fn ord_bool() {
+        let yes = Coin::from(true);
+        let no = Coin::from_bits(0b0001_1000);
+
+        assert!(no < true);
+        assert!(yes > false);
+        assert!(yes <= true);
+        assert!(false < yes);
+        assert!(true > no);
+    }
+
+    #[test]
+    fn hash_does_not_mutate() {
+        use core::hash::{Hash, Hasher};
+        use std::hash::DefaultHasher;
+
+        let hash = |coin: &Coin| {
+            let mut hasher = DefaultHasher::new();
+            coin.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let coin = Coin::from_bits(0b1101_0111);
+        assert_eq!(hash(&coin), hash(&Coin::from(true)));
+        assert_eq!(coin.as_bits(), 0b1101_0111);
+
+        let coin = Coin::from_bits(0b0000_1001);
+        assert_eq!(hash(&coin), hash(&Coin::from(false)));
+        assert_eq!(coin.as_bits(), 0b0000_1001);
+    }
+
+    #[test]
+    fn eq_does_not_mutate() {
+        let a = Coin::from_bits(0b1110_1101);
+        let b = Coin::from_bits(0b0111_1011);
+        assert!(a == b);
+        assert_eq!(a.as_bits(), 0b1110_1101);
+        assert_eq!(b.as_bits(), 0b0111_1011);
+
+        let c = Coin::from_bits(0b0010_0001);
+        assert!(a != c);
+        assert!(c == false);
+        assert_eq!(c.as_bits(), 0b0010_0001);
+    }
+
+    #[test]
+    fn flip_bit() {
+        let coin = Coin::from(true);
+        coin.flip_bit(3);
+        assert_eq!(coin.as_bits(), 0b1111_0111);
+        coin.flip_bit(3);
+        assert_eq!(coin.as_bits(), u8::MAX);
+
+        let coin = WideCoin::<u64>::from(false);
+        coin.flip_bit(63);
+        assert_eq!(coin.as_bits(), 1 << 63);
+    }
+
+    #[test]
+    #[should_panic]
+    fn flip_bit_out_of_range() {
+        Coin::from(true).flip_bit(8);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn flip_n_random() {
+        let mut rng = rand::rngs::mock::StepRng::new(7, 13);
+
+        for n in 0..=8 {
+            let coin = Coin::from(false);
+            coin.flip_n_random(n, &mut rng);
+            assert_eq!(coin.as_bits().count_ones(), n);
+        }
+
+        let coin = WideCoin::<u32>::from(true);
+        coin.flip_n_random(20, &mut rng);
+        assert_eq!(coin.as_bits().count_zeros(), 20);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_coins_vary() {
+        use rand::Rng;
+
+        // Successive low bytes step through every value.
+        let mut rng = rand::rngs::mock::StepRng::new(3, 0x9e37_79b9_7f4a_7c15);
+        let coins: Vec<Coin> = (0..256).map(|_| rng.gen()).collect();
+
+        let trues = coins.iter().filter(|coin| coin.to_bool()).count();
+        assert!(trues > 0 && trues < coins.len(), "{trues} of {} decoded as true", coins.len());
+        assert!(coins.iter().any(|coin| !coin.is_canonical()));
+
+        let wide = WideCoin::<u32>::random(&mut rng);
+        assert!(!wide.is_canonical());
+    }
+
+    #[test]
+    fn reinforce() {
+        for (bits, canonical) in [(0b1101_1111, u8::MAX), (0b1011_0111, u8::MAX), (0b0100_1000, u8::MIN)] {
+            let coin = Coin::from_bits(bits);
+            assert!(coin.reinforce(), "{bits:#010b}");
+            assert_eq!(coin.as_bits(), canonical);
+        }
+
+        for bits in [0b1011_0101, 0b1001_0110, 0b1000_0101] {
+            let coin = Coin::from_bits(bits);
+            assert!(!coin.reinforce(), "{bits:#010b}");
+            assert_eq!(coin.as_bits(), bits);
+        }
+
+        assert!(Coin::from(true).reinforce());
+    }
+
+    #[test]
+    fn scrub_with() {
+        let mut reports = Vec::new();
+
+        Coin::from(true).scrub_with(|flips| reports.push(flips));
+        Coin::from(false).scrub_with(|flips| reports.push(flips));
+        assert!(reports.is_empty());
+
+        let coin = Coin::from_bits(0b0100_0100);
+        coin.scrub_with(|flips| reports.push(flips));
+        coin.scrub_with(|flips| reports.push(flips));
+        assert_eq!(reports, [2]);
+    }
+
+    #[test]
+    fn parse_flexible() {
+        let cases = [
+            ("true", true),
+            ("TRUE", true),
+            ("1", true),
+            ("on", true),
+            ("On", true),
+            ("yes", true),
+            ("YES", true),
+            ("false", false),
+            ("False", false),
+            ("0", false),
+            ("off", false),
+            ("OFF", false),
+            ("no", false),
+            ("nO", false),
+        ];
+
+        for (s, b) in cases {
+            let coin = Coin::parse_flexible(s).unwrap();
+            assert_eq!(coin.as_bits(), super::canonical::<u8>(b), "{s}");
+        }
+    }
+
+    #[test]
+    fn parse_flexible_rejects_other_words() {
+        for s in ["", "y", "2", "enabled", "on "] {
+            assert!(Coin::parse_flexible(s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn default_is_canonical_false() {
+        let coin = Coin::default();
+        assert!(!coin.to_bool());
+        assert_eq!(coin.as_bits(), 0);
+    }
+
+    #[test]
+    fn not() {
+        assert_eq!(!Coin::from(true), Coin::from(false));
+        assert_eq!(!Coin::from(false), Coin::from(true));
+    }
+
+    #[test]
+    fn not_with_two_bits_flipped() {
+        let coin = Coin::from_bits(0b1110_1110);
+        assert_eq!((!&coin).as_bits(), u8::MIN);
+
+        let coin = Coin::from_bits(0b0100_0001);
+        assert_eq!((!coin).as_bits(), u8::MAX);
+    }
+
+    #[test]
+    fn not_decodes_marginal_bits() {
+        let coin = Coin::from_bits(0b0001_1111);
+        assert!(!(!coin).to_bool());
+    }
+
+    #[test]
+    fn bitand_truth_table() {
+        for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+            assert_eq!(Coin::from(a) & Coin::from(b), Coin::from(a & b));
+
+            let mut coin = Coin::from(a);
+            coin &= Coin::from(b);
+            assert_eq!(coin.to_bool(), a & b);
+        }
+    }
+
+    #[test]
+    fn bitand_returns_canonical_bits() {
+        let a = Coin::from_bits(0b0111_1000);
+        let b = Coin::from_bits(0b1100_0110);
+        assert_eq!((a & b).as_bits(), u8::MAX);
+
+        let a = Coin::from_bits(0b1011_1101);
+        let b = Coin::from_bits(0b0010_0100);
+        assert_eq!((&a & &b).as_bits(), u8::MIN);
+    }
+
+    #[test]
+    fn bitor_truth_table() {
+        for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+            assert_eq!(Coin::from(a) | Coin::from(b), Coin::from(a | b));
+            assert_eq!(&Coin::from(a) | &Coin::from(b), Coin::from(a | b));
+
+            let mut coin = Coin::from(a);
+            coin |= Coin::from(b);
+            assert_eq!(coin.to_bool(), a | b);
+        }
+    }
+
+    #[test]
+    fn bitor_with_decoded_false() {
+        let a = Coin::from(true);
+        let b = Coin::from_bits(0b0000_0111);
+        assert_eq!((&a | &b).as_bits(), u8::MAX);
+    }
+
+    #[test]
+    fn combinator_chains() {
+        let (t, f) = (|| Coin::from(true), || Coin::from(false));
+
+        assert_eq!(t().and(Coin::from_bits(0b1110_1101)).xor(f()).as_bits(), u8::MAX);
+        assert_eq!(Coin::from_bits(0b0000_1001).or(f()).and(t()).as_bits(), u8::MIN);
+
+        for bits in 0..8 {
+            let (a, b, c) = (bits & 1 != 0, bits & 2 != 0, bits & 4 != 0);
+            let coin = Coin::from;
+            assert_eq!(coin(a).and(coin(b)).and(coin(c)), coin(a).and(coin(b).and(coin(c))));
+            assert_eq!(coin(a).or(coin(b)).or(coin(c)), coin(a).or(coin(b).or(coin(c))));
+            assert_eq!(coin(a).xor(coin(b)).xor(coin(c)), coin(a).xor(coin(b).xor(coin(c))));
+            assert_eq!(coin(a).and(coin(b)).or(coin(c)).to_bool(), a && b || c);
+            assert_eq!(coin(a).xor(coin(b)).to_bool(), a ^ b);
+        }
+    }
+
+    #[test]
+    fn checked_ops_flag_marginal_operands() {
+        let confident = || Coin::from_bits(0b1111_0111);
+        let marginal = || Coin::from_bits(0b0011_1000);
+
+        let (coin, degraded) = confident().and_checked(&marginal());
+        assert_eq!(coin, Coin::from(false));
+        assert!(degraded);
+        let (coin, degraded) = marginal().or_checked(&confident());
+        assert_eq!(coin, Coin::from(true));
+        assert!(degraded);
+
+        let (coin, degraded) = confident().and_checked(&Coin::from(false));
+        assert_eq!(coin.as_bits(), u8::MIN);
+        assert!(!degraded);
+        let (coin, degraded) = Coin::from_bits(0b0000_0001).or_checked(&Coin::from(false));
+        assert_eq!(coin.as_bits(), u8::MIN);
+        assert!(!degraded);
+    }
+
+    #[test]
+    fn bitxor_truth_table() {
+        for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+            assert_eq!(Coin::from(a) ^ Coin::from(b), Coin::from(a ^ b));
+
+            let mut coin = Coin::from(a);
+            coin ^= Coin::from(b);
+            assert_eq!(coin.to_bool(), a ^ b);
+        }
+    }
+
+    #[test]
+    fn bitxor_with_itself_is_canonical_false() {
+        for bits in [u8::MAX, u8::MIN, 0b0011_1110, 0b0000_0100] {
+            let coin = Coin::from_bits(bits);
+            assert_eq!((&coin ^ &coin).as_bits(), u8::MIN);
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    proptest::proptest! {
+        #[test]
+        fn tolerates_up_to_three_flips(
+            b: bool,
+            positions in proptest::sample::subsequence((0..8).collect::<Vec<u32>>(), 0..=3),
+        ) {
+            let mut bits: u8 = super::canonical(b);
+            for position in positions {
+                bits ^= 1 << position;
+            }
+
+            proptest::prop_assert_eq!(Coin::from_bits(bits).to_bool(), b);
+        }
+    }
+
+    #[test]
+    fn some_four_flips_break_decoding() {
+        let breaking = [true, false].into_iter().find_map(|b| {
+            (0..=u8::MAX)
+                .filter(|flips| flips.count_ones() == 4)
+                .find(|&flips| Coin::from_bits(super::canonical::<u8>(b) ^ flips).to_bool() != b)
+        });
+        assert!(breaking.is_some());
+    }
+
+    #[test]
+    fn u16_tolerates_half_its_bits_flipped() {
+        let coin = WideCoin::<u16>::from_bits(0xff00);
+        assert!(coin.to_bool());
+
+        let coin = WideCoin::<u16>::from_bits(0x7f00);
+        assert!(!coin.to_bool());
+    }
+
+    #[test]
+    fn u32_tolerates_half_its_bits_flipped() {
+        let coin = WideCoin::<u32>::from_bits(0xffff_0000);
+        assert!(coin.to_bool());
+
+        let coin = WideCoin::<u32>::from_bits(0x7fff_0000);
+        assert!(!coin.to_bool());
+    }
+
+    #[test]
+    fn u64_tolerates_half_its_bits_flipped() {
+        let coin = WideCoin::<u64>::from_bits(0xffff_ffff_0000_0000);
+        assert!(coin.to_bool());
+
+        let coin = WideCoin::<u64>::from_bits(0x7fff_ffff_0000_0000);
+        assert!(!coin.to_bool());
+    }
+
+    #[test]
+    fn u64_fails_past_half_its_bits_flipped() {
+        let coin = WideCoin::<u64>::from(true);
+        for bit in 0..32 {
+            coin.flip_bit(bit);
+        }
+        assert!(coin.to_bool());
+
+        coin.flip_bit(32);
+        assert!(!coin.to_bool());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn bools_round_trip() {
+        let bools = [true, false, false, true, true, false, true];
+        let coins = super::from_bools(&bools);
+        assert!(coins.iter().all(|coin| coin.is_canonical()));
+        assert_eq!(super::to_bools(&coins), bools);
+
+        for (index, bit) in [(0, 3), (0, 6), (2, 2), (2, 6), (5, 4)] {
+            coins[index].flip_bit(bit);
+        }
+        assert_eq!(super::to_bools(&coins), bools);
+    }
+
+    #[cfg(feature = "bitvec")]
+    #[test]
+    fn bitvec_round_trip() {
+        use bitvec::prelude::*;
+
+        let bits = bitvec![1, 0, 0, 1, 1, 1, 0, 1, 0, 0, 1];
+        let coins = super::from_bitslice(&bits);
+        assert_eq!(coins.len(), bits.len());
+        for (coin, bit) in coins.iter().zip(bits.iter()) {
+            assert!(coin.is_canonical());
+            assert_eq!(coin.to_bool(), *bit);
+        }
+        assert_eq!(super::to_bitvec(&coins), bits);
+
+        let bytes = [0b1010_0110_u8, 0x0f];
+        let coins = super::from_bitslice(bytes.view_bits::<Msb0>());
+        assert_eq!(super::to_bitvec(&coins), bytes.view_bits::<Msb0>());
+    }
+
+    #[cfg(feature = "bitvec")]
+    #[test]
+    fn bitvec_reconstructed_from_flipped_coins() {
+        use bitvec::prelude::*;
+
+        let bits = bitvec![0, 1, 1, 0, 1, 0, 0, 0, 1];
+        let coins = super::from_bitslice(&bits);
+        for (i, coin) in coins.iter().enumerate() {
+            coin.flip_bit(i as u8 % 8);
+            coin.flip_bit((i as u8 + 3) % 8);
+            coin.flip_bit((i as u8 + 5) % 8);
+        }
+
+        assert_eq!(super::to_bitvec(&coins), bits);
+    }
+
+    #[test]
+    fn vote() {
+        assert_eq!(Coin::vote(&[true; 5]).as_bits(), u8::MAX);
+        assert_eq!(Coin::vote(&[false; 5]).as_bits(), u8::MIN);
+
+        assert_eq!(Coin::vote(&[true, false, true, true, false]).as_bits(), u8::MAX);
+        assert_eq!(Coin::vote(&[false, false, true, false]).as_bits(), u8::MIN);
+
+        assert_eq!(Coin::vote(&[true, false, false, true]).as_bits(), u8::MAX);
+        assert_eq!(Coin::vote(&[]).as_bits(), u8::MAX);
+
+        assert_eq!(WideCoin::<u64>::vote(&[false, true, false]).as_bits(), u64::MIN);
+    }
+
+    #[test]
+    fn from_bits_checked() {
+        assert!(Coin::from_bits_checked(u8::MAX).unwrap().to_bool());
+        assert!(!Coin::from_bits_checked(u8::MIN).unwrap().to_bool());
+        for bits in [0b1111_1110, 0b0000_0001, 0b1010_1010] {
+            assert_eq!(Coin::from_bits_checked(bits), Err(CoinError::Uncorrectable), "{bits:#010b}");
+            assert_eq!(Coin::from_bits(bits).as_bits(), bits);
+        }
+
+        assert!(WideCoin::<u16>::from_bits_checked(u16::MAX).is_ok());
+        assert!(WideCoin::<u16>::from_bits_checked(0xff00).is_err());
+    }
+
+    #[test]
+    fn weighted_vote() {
+        let corrupted = || (Coin::from_bits(0b0010_0110), 2);
+        let entries = [(Coin::from(true), 7), corrupted(), corrupted(), corrupted()];
+        assert_eq!(Coin::weighted_vote(&entries).as_bits(), u8::MAX);
+
+        let entries = [(Coin::from(true), 5), corrupted(), corrupted(), corrupted()];
+        assert_eq!(Coin::weighted_vote(&entries).as_bits(), u8::MIN);
+
+        let entries = [(Coin::from(true), 4), corrupted(), corrupted()];
+        assert_eq!(Coin::weighted_vote(&entries).as_bits(), u8::MAX);
+
+        let entries = [(Coin::from(false), u32::MAX), (Coin::from(true), u32::MAX), (Coin::from(false), 1)];
+        assert_eq!(Coin::weighted_vote(&entries).as_bits(), u8::MIN);
+        assert_eq!(Coin::weighted_vote(&[]).as_bits(), u8::MAX);
+    }
+
+    #[test]
+    fn is_canonical() {
+        assert!(Coin::from(true).is_canonical());
+        assert!(Coin::from(false).is_canonical());
+        for bits in [0b1111_1110, 0b0000_0001, 0b1010_1010, 0b0111_1111] {
+            assert!(!Coin::from_bits(bits).is_canonical(), "{bits:#010b}");
+        }
+
+        let coin = WideCoin::<u32>::from_bits(0xffff_7fff);
+        assert!(!coin.is_canonical());
+        coin.scrub();
+        assert!(coin.is_canonical());
+    }
+
+    #[test]
+    fn to_canonical_leaves_source_untouched() {
+        for (bits, canonical) in [(0b1110_0111, u8::MAX), (0b0010_1000, u8::MIN), (u8::MAX, u8::MAX)] {
+            let coin = Coin::from_bits(bits);
+            assert_eq!(coin.to_canonical().as_bits(), canonical);
+            assert_eq!(coin.as_bits(), bits);
+        }
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn coins_implement_defmt_format() {
+        fn assert_format<T: defmt::Format>() {}
+
+        assert_format::<Coin>();
+        assert_format::<WideCoin<u16>>();
+        assert_format::<WideCoin<u32>>();
+        assert_format::<WideCoin<u64>>();
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_casts() {
+        use super::CoinSnapshot;
+
+        let bytes = [0xff, 0x00, 0b1101_1011, 0b0001_0000];
+        let snapshots = CoinSnapshot::from_bytes(&bytes);
+        let decoded: Vec<bool> = snapshots.iter().map(|s| s.to_bool()).collect();
+        assert_eq!(decoded, [true, false, true, false]);
+        assert_eq!(CoinSnapshot::as_bytes(snapshots), bytes);
+
+        let snapshots: [CoinSnapshot; 4] = bytemuck::cast(bytes);
+        assert_eq!(bytemuck::cast::<_, [u8; 4]>(snapshots), bytes);
+
+        let coins: [Coin; 3] = bytemuck::Zeroable::zeroed();
+        assert!(coins.iter().all(|coin| !coin.to_bool() && coin.is_canonical()));
+
+        let mut bytes = bytes;
+        let cells = core::cell::Cell::from_mut(&mut bytes[..]).as_slice_of_cells();
+        let coins: &[Coin] = bytemuck::TransparentWrapper::wrap_slice(cells);
+        coins[2].scrub();
+        assert_eq!(bytes, [0xff, 0x00, 0xff, 0b0001_0000]);
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        let coin = Coin::from_bits(0b1011_0110);
+        let snapshot = coin.snapshot();
+        let copy = snapshot;
+        assert_eq!(snapshot.as_bits(), 0b1011_0110);
+
+        // Scrubbing the coin doesn't reach the snapshot.
+        coin.scrub();
+        assert_eq!(copy.as_bits(), 0b1011_0110);
+        assert!(copy.to_bool() && bool::from(snapshot));
+
+        let restored = Coin::from(copy);
+        assert_eq!(restored.as_bits(), 0b1011_0110);
+        assert_eq!(restored.to_bool(), copy.to_bool());
+        assert_eq!(restored.snapshot().to_bool(), coin.to_bool());
+    }
+
+    #[test]
+    fn bool_conversions_never_scrub() {
+        let mut coin = Coin::from_bits(0b1101_0110);
+
+        assert!(bool::from(&coin));
+        assert!(bool::from(&mut coin));
+        assert!(coin.clone().into_bool());
+        assert!(bool::from(coin.clone()));
+        assert_eq!(coin.as_bits(), 0b1101_0110);
+
+        let coin = Coin::from_bits(0b0100_1001);
+        let b: bool = (&coin).into();
+        assert!(!b);
+        assert_eq!(coin.as_bits(), 0b0100_1001);
+        assert!(!coin.into_bool());
+    }
+
+    #[test]
+    fn clone_canonical() {
+        let truthy = Coin::from_bits(0b1101_1110);
+        let falsey = Coin::from_bits(0b0100_0001);
+
+        assert_eq!(truthy.clone().as_bits(), 0b1101_1110);
+        assert_eq!(falsey.clone().as_bits(), 0b0100_0001);
+
+        assert_eq!(truthy.clone_canonical().as_bits(), u8::MAX);
+        assert_eq!(falsey.clone_canonical().as_bits(), u8::MIN);
+        assert_eq!(truthy.as_bits(), 0b1101_1110);
+    }
+
+    #[test]
+    fn option_bool_from_coin() {
+        fn decisive(coin: &Coin) -> Option<bool> {
+            let b: bool = Option::from(coin)?;
+            Some(!b)
+        }
+
+        assert_eq!(Option::<bool>::from(&Coin::from_bits(0b1111_1011)), Some(true));
+        assert_eq!(Option::<bool>::from(&Coin::from_bits(0b0000_0100)), Some(false));
+        assert_eq!(Option::<bool>::from(Coin::from_bits(0b1100_0011)), None);
+        assert_eq!(Option::<bool>::from(&Coin::from_bits(0b1100_0111)), None);
+
+        assert_eq!(decisive(&Coin::from(false)), Some(true));
+        assert_eq!(decisive(&Coin::from_bits(0b0000_1110)), None);
+    }
+
+    #[test]
+    fn distance_to_flip() {
+        for (bits, expected) in [
+            (u8::MAX, 5),
+            (0b1111_1110, 4),
+            (0b1110_1110, 3),
+            (0b1010_1110, 2),
+            (0b1010_1010, 1),
+            (0b0000_0111, 1),
+            (0b0010_0001, 2),
+            (0b0001_0000, 3),
+            (u8::MIN, 4),
+        ] {
+            let coin = Coin::from_bits(bits);
+            assert_eq!(coin.distance_to_flip(), expected, "{bits:#010b}");
+        }
+
+        // Flipping that many bits towards the boundary does change the value.
+        let coin = Coin::from_bits(0b1110_1110);
+        for bit in [1, 2, 3] {
+            coin.flip_bit(bit);
+        }
+        assert!(!coin.to_bool());
+
+        assert_eq!(WideCoin::<u64>::from(true).distance_to_flip(), 33);
+        assert_eq!(WideCoin::<u64>::from(false).distance_to_flip(), 32);
+    }
+
+    #[test]
+    fn iter_bits_is_lsb_first() {
+        let coin = Coin::from_bits(0b1100_1010);
+        let bits: Vec<bool> = coin.iter_bits().collect();
+        assert_eq!(bits, [false, true, false, true, false, false, true, true]);
+        assert_eq!(coin.as_bits(), 0b1100_1010);
+
+        let wide = WideCoin::<u16>::from_bits(0x8001);
+        let bits: Vec<bool> = wide.iter_bits().collect();
+        assert_eq!(bits.len(), 16);
+        assert!(bits[0] && bits[15]);
+        assert_eq!(bits.iter().filter(|&&b| b).count(), 2);
+    }
+
+    #[test]
+    fn bit_distance() {
+        let a = Coin::from_bits(0b1011_0110);
+        assert_eq!(a.bit_distance(&Coin::from_bits(0b1011_0110)), 0);
+        assert_eq!(a.bit_distance(&Coin::from_bits(0b1011_0111)), 1);
+        assert_eq!(a.bit_distance(&Coin::from_bits(0b0100_1001)), 8);
+        assert_eq!(Coin::from(true).bit_distance(&Coin::from(false)), 8);
+        assert_eq!(a.as_bits(), 0b1011_0110);
+
+        let wide = WideCoin::<u64>::from(true);
+        assert_eq!(wide.bit_distance(&WideCoin::from_bits(u64::MAX >> 3)), 3);
+    }
+
+    #[test]
+    fn drift_from() {
+        // A `true` coin losing one bit at a time.
+        let coin = Coin::from(true);
+        for bit in 0..8 {
+            assert_eq!(coin.drift_from(true), bit);
+            assert_eq!(coin.drift_from(false), 8 - bit);
+            assert_eq!(coin.to_bool(), bit <= 4, "{bit}");
+            coin.flip_bit(bit as u8);
+        }
+        assert_eq!(coin.drift_from(true), 8);
+
+        let wide = WideCoin::<u16>::from_bits(0x0f0f);
+        assert_eq!(wide.drift_from(true), 8);
+        assert_eq!(wide.drift_from(false), 8);
+    }
+
+    #[test]
+    fn flip_direction() {
+        // Decaying towards zero, including past the decision boundary.
+        for bits in [0b1111_1110, 0b0110_1101, 0b0000_0100] {
+            assert_eq!(Coin::from_bits(bits).flip_direction(true), Some(false), "{bits:#010b}");
+        }
+
+        // Decaying towards one.
+        for bits in [0b0000_1000, 0b0101_0010, 0b1111_1011] {
+            assert_eq!(Coin::from_bits(bits).flip_direction(false), Some(true), "{bits:#010b}");
+        }
+
+        assert_eq!(Coin::from(true).flip_direction(true), None);
+        assert_eq!(Coin::from(false).flip_direction(false), None);
+        assert_eq!(WideCoin::<u32>::from_bits(0x8000_0000).flip_direction(false), Some(true));
+    }
+
+    #[test]
+    fn classify() {
+        use super::Decode;
+
+        for (bits, expected) in [
+            (u8::MAX, Decode::DefinitelyTrue),
+            (0b1111_0111, Decode::DefinitelyTrue),
+            (0b1011_1011, Decode::DefinitelyTrue),
+            (0b1011_1001, Decode::Marginal),
+            (0b1010_1010, Decode::Marginal),
+            (0b1000_1001, Decode::Marginal),
+            (0b1000_0001, Decode::DefinitelyFalse),
+            (0b0001_0000, Decode::DefinitelyFalse),
+            (u8::MIN, Decode::DefinitelyFalse),
+        ] {
+            assert_eq!(Coin::from_bits(bits).classify(), expected, "{bits:#010b}");
+        }
+
+        assert_eq!(WideCoin::<u16>::from_bits(0x07fe).classify(), Decode::DefinitelyTrue);
+        assert_eq!(WideCoin::<u16>::from_bits(0x00ff).classify(), Decode::Marginal);
+        assert_eq!(WideCoin::<u16>::from_bits(0x007f).classify(), Decode::Marginal);
+        assert_eq!(WideCoin::<u16>::from_bits(0x003f).classify(), Decode::DefinitelyFalse);
+    }
+
+    #[test]
+    fn to_bool_checked() {
+        for (bits, expected) in [
+            (u8::MAX, (true, 0)),
+            (u8::MIN, (false, 0)),
+            (0b1111_0111, (true, 1)),
+            (0b0001_1000, (false, 2)),
+            (0b0101_0101, (true, 4)),
+            (0b0100_0101, (false, 3)),
+        ] {
+            let coin = Coin::from_bits(bits);
+            assert_eq!(coin.to_bool_checked(), expected, "{bits:#010b}");
+            assert_eq!(coin.as_bits(), bits);
+        }
+
+        let wide = WideCoin::<u16>::from_bits(0x00f0);
+        assert_eq!(wide.to_bool_checked(), (false, 4));
+    }
+
+    #[test]
+    fn decode_ml() {
+        for (bits, expected) in [
+            (u8::MAX, (true, 0)),
+            (0b1101_1111, (true, 1)),
+            (0b0110_1110, (true, 3)),
+            (0b0000_1000, (false, 1)),
+            (0b1001_0001, (false, 3)),
+            (0b1100_0011, (true, 4)),
+        ] {
+            let coin = Coin::from_bits(bits);
+            assert_eq!(coin.decode_ml(), expected, "{bits:#010b}");
+            assert_eq!(expected.1, bits.count_ones().min(bits.count_zeros()));
+        }
+
+        // A tie costs half the bits, the most any decode can cost.
+        assert_eq!(WideCoin::<u32>::from_bits(0xffff).decode_ml(), (true, 16));
+    }
+
+    #[test]
+    fn scrub_all() {
+        let coins = [
+            Coin::from(true),
+            Coin::from_bits(0b1110_1111),
+            Coin::from(false),
+            Coin::from_bits(0b0010_0100),
+            Coin::from_bits(0b1011_0111),
+        ];
+
+        assert_eq!(super::scrub_all(&coins), 1 + 2 + 2);
+        let bits: Vec<u8> = coins.iter().map(Coin::as_bits).collect();
+        assert_eq!(bits, [u8::MAX, u8::MAX, u8::MIN, u8::MIN, u8::MAX]);
+        assert_eq!(super::scrub_all(&coins), 0);
+    }
+
+    #[test]
+    fn all_and_any() {
+        let empty: [Coin; 0] = [];
+        assert!(super::all(&empty));
+        assert!(!super::any(&empty));
+
+        let trues = [Coin::from(true), Coin::from(true), Coin::from(true)];
+        assert!(super::all(&trues));
+        assert!(super::any(&trues));
+
+        let falses = [Coin::from(false), Coin::from(false)];
+        assert!(!super::all(&falses));
+        assert!(!super::any(&falses));
+
+        let mixed = [Coin::from(true), Coin::from_bits(0b0100_1000), Coin::from_bits(0b1110_1101)];
+        assert!(!super::all(&mixed));
+        assert!(super::any(&mixed));
+        assert!(super::all(&mixed[2..]));
+        assert_eq!(mixed[1].as_bits(), 0b0100_1000);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json_round_trip() {
+        use std::string::ToString;
+
+        for b in [true, false] {
+            let json = serde_json::to_string(&Coin::from(b)).unwrap();
+            assert_eq!(json, b.to_string());
+
+            let coin: Coin = serde_json::from_str(&json).unwrap();
+            assert_eq!(coin.to_bool(), b);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bincode_round_trip() {
+        for b in [true, false] {
+            let bytes = bincode::serialize(&WideCoin::<u32>::from(b)).unwrap();
+            let coin: WideCoin<u32> = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(coin.to_bool(), b);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bincode_round_trip_decodes_corrupted_coins() {
+        for (bits, b) in [(0b1101_0111, true), (0b0100_1000, false), (0b0001_1110, true)] {
+            let coin = Coin::from_bits(bits);
+            let bytes = bincode::serialize(&coin).unwrap();
+            assert_eq!(bytes, [u8::from(b)], "serializes the decoded bool, not the raw byte");
+
+            let coin: Coin = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(coin.to_bool(), b);
+            assert_eq!(coin.as_bits(), super::canonical::<u8>(b));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_deserializes_handwritten_json() {
+        let coin: Coin = serde_json::from_str("true").unwrap();
+        assert_eq!(coin.as_bits(), u8::MAX);
+
+        let coin: Coin = serde_json::from_str("false").unwrap();
+        assert_eq!(coin.as_bits(), u8::MIN);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_does_not_preserve_corruption() {
+        let coin = Coin::from_bits(0b1011_0111);
+
+        let json = serde_json::to_string(&coin).unwrap();
+        let coin: Coin = serde_json::from_str(&json).unwrap();
+        assert_eq!(coin.as_bits(), u8::MAX);
+    }
+}
@@ -0,0 +1,145 @@
+//! Serde wrappers that write a coin's raw bits.
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Word, WideCoin};
+
+/// A coin that is serialized as its bits, and rejects non-canonical bits
+///
+/// [`WideCoin`]'s own `Serialize` writes the decoded `bool`. `StrictCoin`
+/// writes the canonical bit pattern instead, so the wire format keeps the
+/// coin's redundancy. When reading, anything other than all ones or all
+/// zeros is an error, which suits formats where corruption in transit must
+/// be reported rather than corrected. [`TolerantCoin`] reads the same
+/// format, but decodes corrupted bits.
+///
+/// ```
+/// # use coin::StrictCoin;
+/// let coin: StrictCoin<u8> = serde_json::from_str("255").unwrap();
+/// assert!(coin.into_inner().to_bool());
+///
+/// assert!(serde_json::from_str::<StrictCoin<u8>>("239").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrictCoin<W: Word>(WideCoin<W>);
+
+/// A coin that is serialized as its bits, and decodes corrupted bits
+///
+/// Reads the same format as [`StrictCoin`], but decodes any bits by counting
+/// them, as [`WideCoin::to_bool`] does. The coin that is read back is always
+/// canonical.
+///
+/// ```
+/// # use coin::TolerantCoin;
+/// let coin: TolerantCoin<u8> = serde_json::from_str("239").unwrap();
+/// assert_eq!(coin.into_inner().as_bits(), 0xff);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TolerantCoin<W: Word>(WideCoin<W>);
+
+impl<W: Word> StrictCoin<W> {
+    #[inline]
+    pub fn into_inner(self) -> WideCoin<W> {
+        self.0
+    }
+}
+
+impl<W: Word> TolerantCoin<W> {
+    #[inline]
+    pub fn into_inner(self) -> WideCoin<W> {
+        self.0
+    }
+}
+
+impl<W: Word> From<WideCoin<W>> for StrictCoin<W> {
+    #[inline]
+    fn from(coin: WideCoin<W>) -> Self {
+        StrictCoin(coin)
+    }
+}
+
+impl<W: Word> From<WideCoin<W>> for TolerantCoin<W> {
+    #[inline]
+    fn from(coin: WideCoin<W>) -> Self {
+        TolerantCoin(coin)
+    }
+}
+
+/// Writes the canonical bit pattern for the decoded value.
+impl<W: Word + Serialize> Serialize for StrictCoin<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.clone_canonical().as_bits().serialize(serializer)
+    }
+}
+
+/// Writes the canonical bit pattern for the decoded value.
+impl<W: Word + Serialize> Serialize for TolerantCoin<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.clone_canonical().as_bits().serialize(serializer)
+    }
+}
+
+/// Reads bits that must be a canonical pattern.
+impl<'de, W: Word + Deserialize<'de>> Deserialize<'de> for StrictCoin<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bits = W::deserialize(deserializer)?;
+        match WideCoin::from_bits_checked(bits) {
+            Ok(coin) => Ok(StrictCoin(coin)),
+            Err(_) => Err(D::Error::custom(format_args!(
+                "coin bits {bits:#b} are not a canonical pattern"
+            ))),
+        }
+    }
+}
+
+/// Reads any bits, decoding them into a canonical coin.
+impl<'de, W: Word + Deserialize<'de>> Deserialize<'de> for TolerantCoin<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bits = W::deserialize(deserializer)?;
+        Ok(TolerantCoin(WideCoin::from_bits(bits).clone_canonical()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StrictCoin, TolerantCoin};
+    use crate::WideCoin;
+    use std::string::ToString;
+
+    #[test]
+    fn canonical_bits_round_trip() {
+        for b in [true, false] {
+            let json = serde_json::to_string(&StrictCoin::from(WideCoin::<u8>::from(b))).unwrap();
+            assert_eq!(json, if b { "255" } else { "0" });
+
+            let strict: StrictCoin<u8> = serde_json::from_str(&json).unwrap();
+            let tolerant: TolerantCoin<u8> = serde_json::from_str(&json).unwrap();
+            assert_eq!(strict.into_inner().to_bool(), b);
+            assert_eq!(tolerant.into_inner().to_bool(), b);
+        }
+    }
+
+    #[test]
+    fn corrupted_bits_are_rejected_or_decoded() {
+        for (bits, b) in [(0b1110_1111_u8, true), (0b0100_1000, false)] {
+            let bytes = [bits];
+
+            let err = bincode::deserialize::<StrictCoin<u8>>(&bytes).unwrap_err();
+            assert!(err.to_string().contains("not a canonical pattern"), "{err}");
+
+            let coin = bincode::deserialize::<TolerantCoin<u8>>(&bytes).unwrap().into_inner();
+            assert_eq!(coin.to_bool(), b);
+            assert!(coin.is_canonical());
+        }
+    }
+
+    #[test]
+    fn serializes_canonical_bits() {
+        let coin = TolerantCoin::from(WideCoin::<u16>::from_bits(0xfeff));
+        assert_eq!(bincode::serialize(&coin).unwrap(), [0xff, 0xff]);
+
+        let coin = StrictCoin::from(WideCoin::<u16>::from_bits(0x0100));
+        assert_eq!(bincode::serialize(&coin).unwrap(), [0x00, 0x00]);
+    }
+}
@@ -0,0 +1,247 @@
+//! The backing words of a [`WideCoin`], and the decoding rule they share.
+
+use core::fmt;
+use core::ops::BitXor;
+
+use crate::single::scrub_all_scalar;
+use crate::WideCoin;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type that can back a [`WideCoin`].
+///
+/// Wider words trade space for resilience. A coin backed by a word of
+/// `BITS` bits tolerates `BITS / 2 - 1` bit flips, so a `u64`-backed coin
+/// survives 31 flips where a `u8`-backed one survives 3.
+///
+/// This trait is sealed. It is implemented for `u8`, `u16`, `u32` and `u64`.
+pub trait Word:
+    sealed::Sealed
+    + Copy
+    + Eq
+    + BitXor<Output = Self>
+    + fmt::Debug
+    + fmt::Binary
+    + fmt::LowerHex
+    + fmt::UpperHex
+{
+    /// The canonical `true` pattern, every bit set.
+    const ONES: Self;
+
+    /// The canonical `false` pattern, every bit clear.
+    const ZEROS: Self;
+
+    /// The number of bits in the word.
+    const BITS: u32;
+
+    fn count_ones(self) -> u32;
+
+    fn count_zeros(self) -> u32;
+
+    #[doc(hidden)]
+    fn flip_bit(self, index: u32) -> Self;
+
+    #[doc(hidden)]
+    fn bit(self, index: u32) -> bool;
+
+    #[doc(hidden)]
+    fn scrub_slice(coins: &[WideCoin<Self>]) -> u64 {
+        scrub_all_scalar(coins)
+    }
+}
+
+macro_rules! impl_word {
+    ($($t:ty $({ $($item:item)* })?),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl Word for $t {
+                const ONES: Self = <$t>::MAX;
+                const ZEROS: Self = <$t>::MIN;
+                const BITS: u32 = <$t>::BITS;
+
+                #[cfg(not(feature = "sw_popcount"))]
+                #[inline(always)]
+                fn count_ones(self) -> u32 {
+                    <$t>::count_ones(self)
+                }
+
+                #[cfg(not(feature = "sw_popcount"))]
+                #[inline(always)]
+                fn count_zeros(self) -> u32 {
+                    <$t>::count_zeros(self)
+                }
+
+                #[cfg(feature = "sw_popcount")]
+                #[inline(always)]
+                fn count_ones(self) -> u32 {
+                    sw_count_ones(u64::from(self))
+                }
+
+                #[cfg(feature = "sw_popcount")]
+                #[inline(always)]
+                fn count_zeros(self) -> u32 {
+                    sw_count_ones(u64::from(!self))
+                }
+
+                #[inline(always)]
+                fn flip_bit(self, index: u32) -> Self {
+                    self ^ (1 << index)
+                }
+
+                #[inline(always)]
+                fn bit(self, index: u32) -> bool {
+                    self & (1 << index) != 0
+                }
+
+                $($($item)*)?
+            }
+        )*
+    };
+}
+
+impl_word!(
+    u8 {
+        #[cfg(feature = "simd")]
+        #[inline]
+        fn scrub_slice(coins: &[WideCoin<u8>]) -> u64 {
+            swar::scrub_all(coins)
+        }
+    },
+    u16,
+    u32,
+    u64
+);
+
+/// Counts the set bits of `x` using only shifts, masks, additions and a
+/// subtraction.
+///
+/// `count_ones` lowers to a popcount instruction where the target has one,
+/// and otherwise to a call into a support library. The `sw_popcount` feature
+/// decodes with this function instead, so that the whole path is visible
+/// here for review.
+#[cfg(any(feature = "sw_popcount", test))]
+#[inline(always)]
+const fn sw_count_ones(x: u64) -> u32 {
+    // Each 2-bit field holds the count of its own bits: `0b11` becomes
+    // `0b10`, and so on. The subtraction can't borrow across fields.
+    let x = x - ((x >> 1) & 0x5555_5555_5555_5555);
+    // Add pairs of 2-bit counts into 4-bit fields, then pairs of those into
+    // bytes. No count overflows its field.
+    let x = (x & 0x3333_3333_3333_3333) + ((x >> 2) & 0x3333_3333_3333_3333);
+    let x = (x + (x >> 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    // Fold the eight byte counts into the low byte, without a multiply.
+    let x = x + (x >> 8);
+    let x = x + (x >> 16);
+    let x = x + (x >> 32);
+    (x & 0x7f) as u32
+}
+
+/// Decoding eight bytes at a time, treating them as the lanes of a single
+/// `u64`.
+pub(crate) mod swar {
+    #[cfg(feature = "simd")]
+    use super::{WideCoin, Word};
+
+    const LOW_BITS: u64 = 0x0101_0101_0101_0101;
+
+    /// Computes the canonical pattern for each byte of `word`.
+    #[inline(always)]
+    pub(crate) fn canonical_bytes(word: u64) -> u64 {
+        // Count the ones in each byte, in place.
+        let x = word - ((word >> 1) & (LOW_BITS * 0x55));
+        let x = (x & (LOW_BITS * 0x33)) + ((x >> 2) & (LOW_BITS * 0x33));
+        let x = (x + (x >> 4)) & (LOW_BITS * 0x0f);
+
+        // A byte with 4 or more ones carries into its bit 3 when 4 is added.
+        let truthy = ((x + LOW_BITS * 4) >> 3) & LOW_BITS;
+        truthy * 0xff
+    }
+
+    #[cfg(feature = "simd")]
+    pub(crate) fn scrub_all(coins: &[WideCoin<u8>]) -> u64 {
+        let chunks = coins.chunks_exact(8);
+        let rest = chunks.remainder();
+
+        let mut corrected = 0;
+        for chunk in chunks {
+            let word = u64::from_ne_bytes(core::array::from_fn(|i| chunk[i].as_bits()));
+            let fresh = canonical_bytes(word);
+            if word != fresh {
+                for (coin, byte) in chunk.iter().zip(fresh.to_ne_bytes()) {
+                    coin.set(byte != 0);
+                }
+                corrected += u64::from(Word::count_ones(word ^ fresh));
+            }
+        }
+
+        corrected + super::scrub_all_scalar(rest)
+    }
+}
+
+#[inline(always)]
+pub(crate) fn decode<W: Word>(val: W) -> bool {
+    val.count_ones() >= val.count_zeros() // call twice to avoid baking a constant (4) into the binary
+    // TODO: what if a bit in the opcode flips?
+}
+
+#[inline(always)]
+pub(crate) fn confidence<W: Word>(val: W) -> u8 {
+    val.count_ones().abs_diff(val.count_zeros()) as u8
+}
+
+#[inline(always)]
+pub(crate) fn canonical<W: Word>(b: bool) -> W {
+    // TODO: what if bits in these constants accumulate errors?
+    match b {
+        true => W::ONES,
+        false => W::ZEROS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Word;
+
+    #[test]
+    fn sw_popcount_matches_count_ones() {
+        for byte in 0..=u8::MAX {
+            assert_eq!(super::sw_count_ones(u64::from(byte)), byte.count_ones(), "{byte:#010b}");
+            assert_eq!(Word::count_ones(byte), byte.count_ones());
+            assert_eq!(Word::count_zeros(byte), byte.count_zeros());
+        }
+
+        for word in [u64::MAX, u64::MAX >> 1, 0x8000_0000_0000_0001, 0xdead_beef_0bad_f00d] {
+            assert_eq!(super::sw_count_ones(word), word.count_ones(), "{word:#x}");
+            assert_eq!(Word::count_zeros(word), word.count_zeros());
+        }
+        assert_eq!(Word::count_zeros(0x00ff_u16), 8);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn swar_scrub_matches_scalar() {
+        use std::vec::Vec;
+
+        type Coin = crate::WideCoin<u8>;
+
+        // Every byte value, followed by noise, with a length that leaves a remainder.
+        let mut seed = 0x9e37_79b9_u32;
+        let bytes: Vec<u8> = (0..=u8::MAX)
+            .chain((0..100_003).map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (seed >> 16) as u8
+            }))
+            .collect();
+
+        let swar: Vec<Coin> = bytes.iter().copied().map(Coin::from_bits).collect();
+        let scalar: Vec<Coin> = bytes.iter().copied().map(Coin::from_bits).collect();
+
+        assert_eq!(super::swar::scrub_all(&swar), crate::single::scrub_all_scalar(&scalar));
+        for (a, b) in swar.iter().zip(&scalar) {
+            assert_eq!(a.as_bits(), b.as_bits());
+        }
+    }
+}